@@ -0,0 +1,516 @@
+/*
+ * oj-tables.rs: Build lookup tables for the table-driven evaluators
+ *
+ * Usage: oj-tables [options] SCALE
+ *
+ * Every hand of the given size is dealt from the scale's deck and valued
+ * with the reference evaluator. Distinct values are sorted best first and
+ * numbered as equivalence classes starting at 1, and the class-to-value
+ * table is written as Rust source ("table 2"). Scales whose tables are
+ * shared with a bug game also get classes for five of a kind in every
+ * rank, as the tables shipped in `src/poker/tables/` have. Then each hand's class is
+ * stored at the index given by its perfect hash, and that table is written
+ * as gzip-compressed little-endian u16 values ("table 1" for complete hands,
+ * "table 3" for 7-card hands). Unused hash values get class 0.
+ *
+ * Each binary table is also added to the manifest file in the output
+ * directory with its size and checksum. With --uncompressed, the table is
+ * also written uncompressed (".bin") for memory-mapping. Copy the outputs into
+ * `src/poker/tables/` and `$CARGO_HOME/onejoker/` respectively.
+ *
+ * With --verify, nothing is written: the installed binary table is loaded
+ * (checking it against the manifest), and for every hand the value of the
+ * class it stores, looked up in the crate's own class table, is checked
+ * against the reference evaluator, reporting any hands that don't match.
+ * This needs the table feature for the scale (e.g. "high-hand-tables").
+ *
+ * Examples:
+ *   oj-tables high                        ojp_hh_table_1.bin.gz, _2.rs
+ *   oj-tables -n 7 deuce-to-seven         ojp_kc_table_3.bin.gz
+ *   oj-tables -H mp5-english -p ojp_ll ace-to-six
+ *   oj-tables -c badugi                   ojp_bg_table_2.rs only
+ *   oj-tables -v -n 7 high                check installed ojp_hh_table_3
+ */
+
+use onejoker::prelude::*;
+use onejoker::cards::hashes::*;
+use onejoker::poker::{ojp_reference_evaluator, ojp_badugi_reference_evaluator,
+    HAND_VALUE_WORST};
+use onejoker::poker::tables::{TableManifest, TableManifestEntry,
+    ojp_read_binary_table, ojp_table_dir, ojp_table_sha256};
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+const USAGE: &str = "\
+Usage: oj-tables [options] SCALE
+
+Options:
+  -n, --cards N         hand size to hash (default: complete hand for scale)
+  -H, --hash NAME       perfect hash to index by (default depends on scale)
+  -t, --table N         table number for file name (default 1, or 3 for 7 cards)
+  -p, --prefix NAME     file name prefix (default depends on scale, e.g. ojp_hh)
+  -o, --output DIR      output directory (default \".\", or the installed
+                        table directory with --verify)
+  -c, --classes-only    write only the equivalence class table source
+  -u, --uncompressed    also write uncompressed table for memory-mapping
+  -v, --verify          check existing table against the reference evaluator
+  -h, --help            print this message
+
+Hashes:
+  mp3-english, mp4-english, mp4-low, mp5-english, mp5-low, mp5-stripped,
+  base13-mp5-low, mp7-english, mp7-low
+";
+
+type PerfectHash = fn(&[Card]) -> u32;
+
+// Perfect hashes, and the number of cards each takes
+const HASHES: [(&str, usize, PerfectHash); 9] = [
+    ("mp3-english", 3, ojh_bitfield_mp3_english),
+    ("mp4-english", 4, ojh_bitfield_mp4_english),
+    ("mp4-low", 4, ojh_bitfield_mp4_low),
+    ("mp5-english", 5, ojh_bitfield_mp5_english),
+    ("mp5-low", 5, ojh_bitfield_mp5_low),
+    ("mp5-stripped", 5, ojh_bitfield_mp5_stripped),
+    ("base13-mp5-low", 5, ojh_base13_mp5_low),
+    ("mp7-english", 7, ojh_bitfield_mp7_english),
+    ("mp7-low", 7, ojh_bitfield_mp7_low),
+];
+
+// File prefix and default hashes for complete and 7-card hands
+fn scale_defaults(scale: Scale) -> (&'static str, Option<&'static str>,
+    Option<&'static str>) {
+    match scale {
+        Scale::HighHand => ("ojp_hh", Some("mp5-english"), Some("mp7-english")),
+        Scale::DeuceToSeven => ("ojp_kc", Some("mp5-english"), Some("mp7-english")),
+        Scale::AceToSix => ("ojp_ll", Some("mp5-low"), Some("mp7-low")),
+        // Ace-to-five 7-card table uses its own layout, not a perfect hash
+        Scale::AceToFive => ("ojp_a5", Some("base13-mp5-low"), None),
+        Scale::Badugi => ("ojp_bg", Some("mp4-low"), Some("mp7-low")),
+        Scale::Badeucy => ("ojp_bc", Some("mp4-english"), Some("mp7-english")),
+        Scale::Stripped => ("ojp_st", Some("mp5-stripped"), None),
+        Scale::ThreeCard => ("ojp_tc", Some("mp3-english"), None),
+        Scale::HighHandBug => ("ojp_hb", None, None),
+        Scale::AceToFiveBug => ("ojp_cl", None, None),
+        Scale::PaiGow => ("ojp_pg", None, None),
+        Scale::ShortDeck => ("ojp_sd", None, None),
+        Scale::Mexican => ("ojp_mx", None, None),
+        Scale::ActionRazz => ("ojp_ar", None, None),
+        Scale::None => ("ojp_xx", None, None),
+    }
+}
+
+struct Options {
+    scale: Scale,
+    cards: usize,
+    hash_name: &'static str,
+    hash: PerfectHash,
+    table: usize,
+    prefix: String,
+    output: PathBuf,
+    classes_only: bool,
+    uncompressed: bool,
+    verify: bool,
+}
+
+fn parse_value<T: std::str::FromStr>(opt: &str, arg: Option<String>)
+-> OjResult<T> {
+    let Some(a) = arg else {
+        return Err(OjError::ParseEmpty(format!("{} needs a value", opt)));
+    };
+    a.parse::<T>().map_err(|_|
+        OjError::ParseOther(format!("bad value for {}: {}", opt, a)))
+}
+
+fn parse_args() -> OjResult<Option<Options>> {
+    let mut cards: Option<usize> = None;
+    let mut hash_text: Option<String> = None;
+    let mut table: Option<usize> = None;
+    let mut prefix: Option<String> = None;
+    let mut output: Option<PathBuf> = None;
+    let mut classes_only = false;
+    let mut uncompressed = false;
+    let mut verify = false;
+    let mut scale_texts: Vec<String> = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return Ok(None);
+            },
+            "-n" | "--cards" => {
+                cards = Some(parse_value(&a, args.next())?);
+            },
+            "-H" | "--hash" => {
+                hash_text = Some(parse_value(&a, args.next())?);
+            },
+            "-t" | "--table" => {
+                table = Some(parse_value(&a, args.next())?);
+            },
+            "-p" | "--prefix" => {
+                prefix = Some(parse_value(&a, args.next())?);
+            },
+            "-o" | "--output" => {
+                output = Some(parse_value(&a, args.next())?);
+            },
+            "-c" | "--classes-only" => {
+                classes_only = true;
+            },
+            "-u" | "--uncompressed" => {
+                uncompressed = true;
+            },
+            "-v" | "--verify" => {
+                verify = true;
+            },
+            _ if a.starts_with('-') => {
+                return Err(OjError::ParseOther(format!("unknown option {}", a)));
+            },
+            _ => scale_texts.push(a),
+        }
+    }
+    if 1 != scale_texts.len() {
+        return Err(OjError::ParseOther("need exactly one scale".into()));
+    }
+    let scale = Scale::by_name(&scale_texts[0]);
+    if Scale::None == scale {
+        return Err(OjError::ParseOther(
+            format!("unknown scale {}", scale_texts[0])));
+    }
+    let (default_prefix, hash5, hash7) = scale_defaults(scale);
+
+    let hash_text = match hash_text {
+        Some(t) => t,
+        None => {
+            let default = match cards {
+                None => hash5,
+                Some(n) if n == scale.complete_hand() => hash5,
+                Some(7) => hash7,
+                Some(_) => None,
+            };
+            let Some(d) = default else {
+                return Err(OjError::ParseOther(format!(
+                    "no default hash for {}, use --hash", scale.name())));
+            };
+            d.to_string()
+        },
+    };
+    let Some(&(hash_name, hash_cards, hash)) =
+        HASHES.iter().find(|(n, _, _)| *n == hash_text) else {
+        return Err(OjError::ParseOther(format!("unknown hash {}", hash_text)));
+    };
+    let cards = cards.unwrap_or(hash_cards);
+    if cards != hash_cards {
+        return Err(OjError::ParseOther(format!(
+            "hash {} takes {} cards, not {}", hash_name, hash_cards, cards)));
+    }
+    if cards < scale.complete_hand() {
+        return Err(OjError::ParseOther(format!(
+            "{} needs at least {} cards", scale.name(), scale.complete_hand())));
+    }
+    let table = table.unwrap_or(if 7 == cards { 3 } else { 1 });
+    let prefix = prefix.unwrap_or(default_prefix.to_string());
+    let output = match output {
+        Some(o) => o,
+        None if verify => ojp_table_dir()?,
+        None => PathBuf::from("."),
+    };
+
+    Ok(Some(Options {
+        scale, cards, hash_name, hash, table, prefix, output, classes_only,
+        uncompressed, verify,
+    }))
+}
+
+// Reference value of a complete hand
+fn reference_value(scale: Scale, h: &Hand) -> HandValue {
+    match scale {
+        Scale::Badugi | Scale::Badeucy => ojp_badugi_reference_evaluator(h),
+        _ => ojp_reference_evaluator(h, scale),
+    }
+}
+
+// Reference value of the best complete hand within a larger one
+fn best_value(scale: Scale, h: &Hand) -> HandValue {
+    let k = scale.complete_hand();
+    if h.len() == k {
+        return reference_value(scale, h);
+    }
+    h.combinations(k).map(|sub| reference_value(scale, &sub))
+        .min().unwrap_or(HAND_VALUE_WORST)
+}
+
+fn progress(label: &str, count: usize) -> OjResult<()> {
+    if 0 == count & 0x1FFFF {
+        print!("\r{}:{:10} hands", label, count);
+        std::io::stdout().flush()?;
+    }
+    Ok(())
+}
+
+// Does the scale's table also serve a game with the bug?
+fn has_bug_game(scale: Scale) -> bool {
+    matches!(scale, Scale::HighHand | Scale::AceToFive)
+}
+
+// Five of a kind in every rank of the deck, using a duplicate card
+fn five_of_a_kind(dt: DeckType) -> Vec<Hand> {
+    let mut ranks: Vec<Rank> = dt.card_list().iter().map(|c| c.rank())
+        .collect();
+    ranks.dedup();
+
+    ranks.iter().map(|&r| Hand::new(dt).init([Suit::Spade, Suit::Heart,
+        Suit::Diamond, Suit::Club, Suit::Club].iter()
+        .map(|&s| Card::from_rank_suit(r, s)))).collect()
+}
+
+// Sorted distinct values of all complete hands; class N is at index N
+fn build_classes(scale: Scale) -> OjResult<Vec<HandValue>> {
+    let deck = Deck::new(scale.deck_type());
+    let mut values: Vec<HandValue> = vec![0];
+    let mut count = 0;
+
+    for h in deck.combinations(scale.complete_hand()) {
+        count += 1;
+        progress("classes", count)?;
+        values.push(reference_value(scale, &h));
+    }
+    println!("\rclasses:{:10} hands", count);
+
+    if has_bug_game(scale) {
+        for h in five_of_a_kind(scale.deck_type()) {
+            values.push(reference_value(scale, &h));
+        }
+    }
+
+    values[1..].sort_unstable();
+    values.dedup();
+    println!("{} equivalence classes", values.len() - 1);
+
+    if values.len() > u16::MAX as usize {
+        return Err(OjError::Internal(format!(
+            "too many equivalence classes: {}", values.len() - 1)));
+    }
+    Ok(values)
+}
+
+fn write_classes(opts: &Options, values: &[HandValue]) -> OjResult<()> {
+    let mut path = opts.output.clone();
+    path.push(format!("{}_table_2.rs", opts.prefix));
+    let mut w = BufWriter::new(File::create(&path)?);
+
+    writeln!(w, "/// Map from equivalence class to hand value")?;
+    writeln!(w, "pub static {}_TABLE_2: [u32; {}] = [",
+        opts.prefix.to_uppercase(), values.len())?;
+
+    for line in values.chunks(8) {
+        let row: Vec<String> = line.iter()
+            .map(|v| format!("{:#08X}", v)).collect();
+        writeln!(w, "    {},", row.join(", "))?;
+    }
+    writeln!(w, "];")?;
+    w.flush()?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+// Call f with every hand, its hash, and its equivalence class
+fn for_each_hand<F>(opts: &Options, values: &[HandValue], mut f: F)
+-> OjResult<()>
+where F: FnMut(&Hand, usize, u16) -> OjResult<()> {
+    let classes: HashMap<HandValue, u16> = values.iter().enumerate()
+        .skip(1).map(|(i, v)| (*v, i as u16)).collect();
+
+    let deck = Deck::new(opts.scale.deck_type());
+    let mut count = 0;
+
+    for h in deck.combinations(opts.cards) {
+        count += 1;
+        progress(opts.hash_name, count)?;
+
+        let v = best_value(opts.scale, &h);
+        let Some(&ec) = classes.get(&v) else {
+            return Err(OjError::Internal(format!("no class for {}", h)));
+        };
+        f(&h, (opts.hash)(&h[..]) as usize, ec)?;
+    }
+    println!("\r{}:{:10} hands", opts.hash_name, count);
+    Ok(())
+}
+
+// Class for every hash value, 0 for unused ones
+fn build_hash_table(opts: &Options, values: &[HandValue]) -> OjResult<Vec<u16>> {
+    let mut table: Vec<u16> = Vec::new();
+
+    for_each_hand(opts, values, |h, hash, ec| {
+        if hash >= table.len() {
+            table.resize(hash + 1, 0);
+        }
+        if 0 != table[hash] && ec != table[hash] {
+            return Err(OjError::Internal(format!(
+                "hash {} collides at {}", opts.hash_name, h)));
+        }
+        table[hash] = ec;
+        Ok(())
+    })?;
+    Ok(table)
+}
+
+fn table_name(opts: &Options) -> String {
+    format!("{}_table_{}.bin.gz", opts.prefix, opts.table)
+}
+
+fn write_hash_table(opts: &Options, table: &[u16]) -> OjResult<()> {
+    let name = table_name(opts);
+    let path = opts.output.join(&name);
+    let file = BufWriter::new(File::create(&path)?);
+    let mut w = GzEncoder::new(file, Compression::best());
+
+    let bytes: Vec<u8> = table.iter().flat_map(|ec| ec.to_le_bytes()).collect();
+    w.write_all(&bytes)?;
+    w.finish()?.flush()?;
+    println!("wrote {} ({} entries)", path.display(), table.len());
+
+    if opts.uncompressed {
+        let path = opts.output.join(name.strip_suffix(".gz").unwrap_or(&name));
+        std::fs::write(&path, &bytes)?;
+        println!("wrote {}", path.display());
+    }
+    let mut manifest = match TableManifest::read(&opts.output) {
+        Ok(m) => m,
+        Err(OjError::IO(_)) => TableManifest::new(),
+        Err(e) => return Err(e),
+    };
+    manifest.set(TableManifestEntry {
+        name, entries: table.len(), sha256: ojp_table_sha256(&bytes),
+    });
+    manifest.write(&opts.output)?;
+    println!("updated manifest in {}", opts.output.display());
+    Ok(())
+}
+
+// Class table compiled into the crate for the scale, if any
+fn shipped_classes(scale: Scale) -> Option<&'static [u32]> {
+    #[allow(unused_imports)]
+    use onejoker::poker::tables::*;
+
+    match scale {
+        #[cfg(feature = "high-hand-tables")]
+        Scale::HighHand => Some(&high_tables::OJP_HH_TABLE_2),
+        #[cfg(feature = "ace-to-five-tables")]
+        Scale::AceToFive => Some(&ace_to_five_tables::OJP_A5_TABLE_2),
+        #[cfg(feature = "deuce-to-seven-tables")]
+        Scale::DeuceToSeven => Some(&deuce_to_seven_tables::OJP_KC_TABLE_2),
+        #[cfg(feature = "deuce-to-seven-tables")]
+        Scale::AceToSix => Some(&deuce_to_seven_tables::OJP_LL_TABLE_2),
+        #[cfg(feature = "badugi-tables")]
+        Scale::Badugi => Some(&badugi_tables::OJP_BG_TABLE_2),
+        #[cfg(feature = "badugi-tables")]
+        Scale::Badeucy => Some(&badugi_tables::OJP_BC_TABLE_2),
+        #[cfg(feature = "three-card-tables")]
+        Scale::ThreeCard => Some(&three_card_tables::OJP_TC_TABLE_2),
+        _ => None,
+    }
+}
+
+const MAX_REPORTED: usize = 20;
+
+fn verify_hash_table(opts: &Options) -> OjResult<()> {
+    let Some(values) = shipped_classes(opts.scale) else {
+        return Err(OjError::NotImplemented(format!(
+            "no class table for {} in this build", opts.scale.name())));
+    };
+    let name = table_name(opts);
+    let manifest = TableManifest::read(&opts.output)?;
+    let Some(entry) = manifest.get(&name) else {
+        return Err(OjError::IO(format!("{} is not in manifest", name)));
+    };
+    let table = ojp_read_binary_table(&opts.output, &name, entry.entries)?;
+    println!("loaded {} ({} entries), checksum ok", name, table.len());
+
+    let deck = Deck::new(opts.scale.deck_type());
+    let mut count = 0;
+    let mut mismatches = 0;
+
+    for h in deck.combinations(opts.cards) {
+        count += 1;
+        progress(opts.hash_name, count)?;
+
+        let v = best_value(opts.scale, &h);
+        let hash = (opts.hash)(&h[..]) as usize;
+        let found = table.get(hash).and_then(|&ec| values.get(ec as usize));
+
+        if Some(&v) != found {
+            mismatches += 1;
+            if mismatches <= MAX_REPORTED {
+                println!("\rmismatch: {} hash {} has value {}, expected {:#08X}",
+                    h, hash, found.map_or("none".into(),
+                    |f| format!("{:#08X}", f)), v);
+            }
+        }
+    }
+    println!("\r{}:{:10} hands", opts.hash_name, count);
+    if mismatches > 0 {
+        return Err(OjError::TestFailure(format!("{} mismatched hands in {}",
+            mismatches, name)));
+    }
+    println!("{} verified", name);
+    Ok(())
+}
+
+fn run(opts: &Options) -> OjResult<()> {
+    if opts.verify {
+        return verify_hash_table(opts);
+    }
+    let values = build_classes(opts.scale)?;
+    write_classes(opts, &values)?;
+
+    if ! opts.classes_only {
+        let table = build_hash_table(opts, &values)?;
+        write_hash_table(opts, &table)?;
+    }
+    Ok(())
+}
+
+fn fail(e: OjError) -> ! {
+    eprintln!("oj-tables: {}\nTry 'oj-tables --help' for more information.", e);
+    std::process::exit(1);
+}
+
+fn main() -> OjResult<()> {
+    let opts = match parse_args() {
+        Ok(Some(o)) => o,
+        Ok(None) => return Ok(()),
+        Err(e) => fail(e),
+    };
+    run(&opts).unwrap_or_else(|e| fail(e));
+    Ok(())
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Class tables built here must match the ones in the source tree, or
+    // hash tables built here would index the wrong classes
+    #[test]
+    fn test_build_classes() -> OjResult<()> {
+        for i in 1..=14 {
+            let scale = Scale::from_u8(i);
+            if scale_defaults(scale).1.is_none() { continue; }
+            let Some(table) = shipped_classes(scale) else { continue; };
+            assert_eq!(build_classes(scale)?, table, "{}", scale.name());
+        }
+        assert_eq!(five_of_a_kind(DeckType::English).len(), 13);
+        Ok(())
+    }
+}
@@ -1,12 +1,211 @@
 /*
  * pec.rs: Poker Equity Calculator command-line tool
+ *
+ * Usage: pec [options] HAND [HAND...]
+ *
+ * Each HAND is a player's known cards, e.g. "AsKd", or "-" for a player
+ * with no known cards. Missing cards for the board and for each player's
+ * hand are dealt from the cards that remain, exhaustively if the number
+ * of deals is small enough, and by Monte Carlo sampling otherwise.
+ *
+ * Examples:
+ *   pec AsKs QhQd                           hold'em, preflop
+ *   pec -b "Kd9h2c" AsKs QhQd               hold'em, on the flop
+ *   pec -u 2 AsKsQsJs 9h9d8h8d              omaha
+ *   pec -B 0 -H 7 "AsAd5c" "KhKc9s"         seven-card stud
+ *   pec -g ace-to-five -B 0 -H 5 As2d3c4h 2s3d4c5h   draw, one to come
  */
 
-// extern crate clap;
-// use clap::{App, Arg};
-
 use onejoker::prelude::*;
+use onejoker::utils::Random;
+use onejoker::poker::{ojp_equity, ojp_equity_deals, EquityGame, EquityMode,
+    EquityResult, HoleUsage};
+
+const DEFAULT_TRIALS: u64 = 100_000;
+const EXHAUSTIVE_LIMIT: u64 = 2_000_000;
+
+const USAGE: &str = "\
+Usage: pec [options] HAND [HAND...]
+
+Options:
+  -g, --game NAME       hand scale to use (default \"high\")
+  -b, --board CARDS     known board cards
+  -d, --dead CARDS      dead cards
+  -B, --board-size N    final board size (default: full hand if needed)
+  -H, --hand-size N     final size of each player's hand (default: largest given)
+  -u, --use N           exactly N hole cards must play, e.g. 2 for omaha
+  -t, --trials N        Monte Carlo trials (default 100000)
+  -s, --seed N          seed for reproducible Monte Carlo results
+  -m, --monte-carlo     always use Monte Carlo, even if enumeration is feasible
+  -h, --help            print this message
+";
+
+struct Options {
+    players: Vec<Hand>,
+    board: Hand,
+    dead: Hand,
+    game: EquityGame,
+    trials: u64,
+    seed: Option<u64>,
+    monte_carlo: bool,
+}
+
+fn parse_value<T: std::str::FromStr>(opt: &str, arg: Option<String>)
+-> OjResult<T> {
+    let Some(a) = arg else {
+        return Err(OjError::ParseEmpty(format!("{} needs a value", opt)));
+    };
+    a.parse::<T>().map_err(|_|
+        OjError::ParseOther(format!("bad value for {}: {}", opt, a)))
+}
+
+fn parse_cards(scale: Scale, text: &str) -> OjResult<Hand> {
+    let mut h = Hand::new(scale.deck_type());
+
+    if "-" == text {
+        return Ok(h);
+    }
+    for c in card_parse(text) {
+        if ! h.push(c) {
+            return Err(OjError::BadHand(format!("too many cards: {}", text)));
+        }
+    }
+    if h.is_empty() {
+        return Err(OjError::ParseEmpty(format!("no cards in \"{}\"", text)));
+    }
+    Ok(h)
+}
+
+fn parse_args() -> OjResult<Option<Options>> {
+    let mut game = String::from("high");
+    let mut board_text = String::new();
+    let mut dead_text = String::new();
+    let mut board_size: Option<usize> = None;
+    let mut hand_size: Option<usize> = None;
+    let mut use_hole: Option<usize> = None;
+    let mut trials = DEFAULT_TRIALS;
+    let mut seed: Option<u64> = None;
+    let mut monte_carlo = false;
+    let mut hand_texts: Vec<String> = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return Ok(None);
+            },
+            "-g" | "--game" => {
+                game = parse_value(&a, args.next())?;
+            },
+            "-b" | "--board" => {
+                board_text = parse_value(&a, args.next())?;
+            },
+            "-d" | "--dead" => {
+                dead_text = parse_value(&a, args.next())?;
+            },
+            "-B" | "--board-size" => {
+                board_size = Some(parse_value(&a, args.next())?);
+            },
+            "-H" | "--hand-size" => {
+                hand_size = Some(parse_value(&a, args.next())?);
+            },
+            "-u" | "--use" => {
+                use_hole = Some(parse_value(&a, args.next())?);
+            },
+            "-t" | "--trials" => {
+                trials = parse_value(&a, args.next())?;
+            },
+            "-s" | "--seed" => {
+                seed = Some(parse_value(&a, args.next())?);
+            },
+            "-m" | "--monte-carlo" => {
+                monte_carlo = true;
+            },
+            _ if a.starts_with('-') && "-" != a => {
+                return Err(OjError::ParseOther(format!("unknown option {}", a)));
+            },
+            _ => hand_texts.push(a),
+        }
+    }
+    if hand_texts.len() < 2 {
+        return Err(OjError::ParseOther("need at least two hands".into()));
+    }
+    let scale = Scale::by_name(&game);
+    if Scale::None == scale {
+        return Err(OjError::ParseOther(format!("unknown game {}", game)));
+    }
+    let mut players: Vec<Hand> = Vec::new();
+    for t in hand_texts.iter() {
+        players.push(parse_cards(scale, t)?);
+    }
+    let board = if board_text.is_empty() {
+        Hand::new(scale.deck_type())
+    } else {
+        parse_cards(scale, &board_text)?
+    };
+    let dead = if dead_text.is_empty() {
+        Hand::new(scale.deck_type())
+    } else {
+        parse_cards(scale, &dead_text)?
+    };
+    let mut game = EquityGame::new(scale);
+    if let Some(n) = board_size { game = game.board_size(n); }
+    if let Some(n) = hand_size { game = game.hand_size(n); }
+    if let Some(n) = use_hole { game = game.usage(HoleUsage::Exactly(n)); }
+
+    Ok(Some(Options {
+        players, board, dead, game, trials, seed, monte_carlo,
+    }))
+}
+
+fn run(opts: &Options) -> OjResult<EquityResult> {
+    let deals = ojp_equity_deals(&opts.players, &opts.board, &opts.dead,
+        opts.game)?;
+    let exhaustive = ! opts.monte_carlo &&
+        deals.is_some_and(|c| c <= EXHAUSTIVE_LIMIT);
+
+    let mode = if exhaustive {
+        EquityMode::Exhaustive
+    } else {
+        EquityMode::MonteCarlo {
+            trials: opts.trials,
+            seed: opts.seed.unwrap_or_else(|| Random::new().next64()),
+        }
+    };
+    ojp_equity(&opts.players, &opts.board, &opts.dead, opts.game, mode)
+}
+
+fn fail(e: OjError) -> ! {
+    eprintln!("pec: {}\nTry 'pec --help' for more information.", e);
+    std::process::exit(1);
+}
 
 fn main() -> OjResult<()> {
+    let opts = match parse_args() {
+        Ok(Some(o)) => o,
+        Ok(None) => return Ok(()),
+        Err(e) => fail(e),
+    };
+    let r = run(&opts).unwrap_or_else(|e| fail(e));
+
+    println!("{}: {} deals, {}", opts.game.scale.name(), r.deals,
+        if r.exhaustive { "exhaustive" } else { "monte carlo" });
+    if opts.board.is_not_empty() {
+        println!("board: [{}]", opts.board);
+    }
+    println!("{:>6}  {:<16} {:>8} {:>8} {:>8}",
+        "player", "hand", "win", "tie", "equity");
+
+    for (i, p) in r.players.iter().enumerate() {
+        print!("{:>6}  {:<16} {:>7.3}% {:>7.3}% {:>7.3}%", i + 1,
+            format!("[{}]", opts.players[i]),
+            100.0 * p.win, 100.0 * p.tie, 100.0 * p.share);
+        if r.exhaustive {
+            println!();
+        } else {
+            println!(" \u{b1}{:.3}%", 100.0 * p.margin);
+        }
+    }
     Ok(())
 }
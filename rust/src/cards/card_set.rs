@@ -0,0 +1,403 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/CardSet) | Set of cards as a 64-bit mask
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign,
+    Sub, SubAssign};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::cards::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/CardSet) | Set of cards as a 64-bit mask
+///
+/// Bit `n` is set if the card with ordinal `n` is in the set, as in
+/// [ojh_bitfield_64co]. Unlike [Hand] and [Deck], a set has no order and
+/// can't hold duplicates, but set operations are single instructions and
+/// nothing is ever allocated, which makes it handy for dead cards, boards,
+/// and the like.
+/// ```rust
+/// use onejoker::prelude::*;
+///
+/// let board: CardSet = hand!("Ah","Kh","7d").into_iter().collect();
+/// let hole: CardSet = hand!("Qh","Jh").into_iter().collect();
+/// let all = board | hole;
+/// assert_eq!(all.len(), 5);
+/// assert_eq!(all.of_suit(Suit::Heart).len(), 4);
+/// assert_eq!((all - board), hole);
+/// assert_eq!(all.to_string(), "7dJhQhKhAh");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CardSet(pub u64);
+
+// Bits of the real cards of each suit, excluding jokers
+const CLUB_MASK: u64 = 0x1111_1111_1111_1110;
+
+impl CardSet {
+    /// Empty set
+    pub const EMPTY: CardSet = CardSet(0);
+
+    /// New empty set
+    pub const fn new() -> CardSet {
+        CardSet(0)
+    }
+
+    /// Set of just the given card
+    pub const fn of_card(c: Card) -> CardSet {
+        CardSet(1 << c.0)
+    }
+
+    /// Set of all the cards of a [DeckType]
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// assert_eq!(CardSet::of_deck_type(DeckType::Spanish).len(), 40);
+    /// ```
+    pub const fn of_deck_type(t: DeckType) -> CardSet {
+        CardSet(t.card_mask())
+    }
+
+    /// Number of cards in the set
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Is the set empty?
+    pub const fn is_empty(&self) -> bool {
+        0 == self.0
+    }
+
+    /// Is the card in the set?
+    pub const fn contains(&self, c: Card) -> bool {
+        0 != self.0 & (1 << c.0)
+    }
+
+    /// Add card to the set, returning `true` if it wasn't already there
+    pub fn insert(&mut self, c: Card) -> bool {
+        let had = self.contains(c);
+        self.0 |= 1 << c.0;
+        ! had
+    }
+
+    /// Remove card from the set, returning `true` if it was there
+    pub fn remove(&mut self, c: Card) -> bool {
+        let had = self.contains(c);
+        self.0 &= ! (1 << c.0);
+        had
+    }
+
+    /// Cards in either set
+    pub const fn union(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    /// Cards in both sets
+    pub const fn intersection(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    /// Cards in this set but not the other
+    pub const fn difference(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & ! other.0)
+    }
+
+    /// Cards in exactly one of the sets
+    pub const fn symmetric_difference(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 ^ other.0)
+    }
+
+    /// Is every card of this set in the other?
+    pub const fn is_subset(&self, other: CardSet) -> bool {
+        0 == self.0 & ! other.0
+    }
+
+    /// Do the sets have no cards in common?
+    pub const fn is_disjoint(&self, other: CardSet) -> bool {
+        0 == self.0 & other.0
+    }
+
+    /// Cards of the given suit
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let s: CardSet = hand!("As","Ks","Qh","Jk").into_iter().collect();
+    /// assert_eq!(s.of_suit(Suit::Spade).to_string(), "KsAs");
+    /// assert!(s.of_suit(Suit::None).is_empty());
+    /// ```
+    pub const fn of_suit(&self, s: Suit) -> CardSet {
+        match s {
+            Suit::None => CardSet(0),
+            _ => CardSet(self.0 & (CLUB_MASK << (s as u8 - 1))),
+        }
+    }
+
+    /// Cards of the given rank
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let s: CardSet = hand!("As","Ad","Qh","Jk").into_iter().collect();
+    /// assert_eq!(s.of_rank(Rank::Ace).len(), 2);
+    /// assert!(s.of_rank(Rank::None).is_empty());
+    /// ```
+    pub const fn of_rank(&self, r: Rank) -> CardSet {
+        match r {
+            Rank::None => CardSet(0),
+            _ => CardSet(self.0 & (0xF << (4 * r as u8))),
+        }
+    }
+
+    /// Mask of the ranks present, with bit `n` set for rank number `n`
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let s: CardSet = hand!("As","Ad","5h","2c").into_iter().collect();
+    /// assert_eq!(s.rank_mask(), (1 << 15) | (1 << 5) | (1 << 2));
+    /// ```
+    pub const fn rank_mask(&self) -> u32 {
+        let mut m = self.0 >> 4;
+        m |= m >> 1;
+        m |= m >> 2;
+
+        // Gather bit 0 of each nibble into consecutive bits
+        let mut out: u32 = 0;
+        let mut r = 1;
+        while r < 16 {
+            out |= ((m >> (4 * (r - 1))) as u32 & 1) << r;
+            r += 1;
+        }
+        out
+    }
+
+    /// Mask of the suits present, with bit `n` set for suit number `n`
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let s: CardSet = hand!("As","Ad","5d").into_iter().collect();
+    /// assert_eq!(s.suit_mask(), (1 << Suit::Spade as u8) | (1 << Suit::Diamond as u8));
+    /// ```
+    pub const fn suit_mask(&self) -> u8 {
+        let mut out = 0;
+        let mut s = 0;
+        while s < 4 {
+            if 0 != self.0 & (CLUB_MASK << s) { out |= 2 << s; }
+            s += 1;
+        }
+        out
+    }
+
+    /// Iterate over the cards in ascending order of ordinal
+    pub const fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+
+    /// New [Hand] of the given [DeckType] holding the cards of the set,
+    /// ascending by ordinal
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let s: CardSet = hand!("2c","As","7d").into_iter().collect();
+    /// assert_eq!(s.to_hand(DeckType::English).to_string(), "2c7dAs");
+    /// ```
+    pub fn to_hand(&self, t: DeckType) -> Hand {
+        Hand::new(t).init(self.iter())
+    }
+
+    /// New unshuffled [Deck] of the given [DeckType] with only the cards
+    /// of the set that belong to it
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let dead: CardSet = hand!("As","Ad").into_iter().collect();
+    /// let live = CardSet::of_deck_type(DeckType::English) - dead;
+    /// assert_eq!(live.to_deck(DeckType::English).len(), 50);
+    /// ```
+    pub fn to_deck(&self, t: DeckType) -> Deck {
+        let mut d = Deck::new(t);
+        d.remove_set(CardSet(! self.0));
+        d
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+    fn bitor(self, other: CardSet) -> CardSet { self.union(other) }
+}
+
+impl BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, other: CardSet) { self.0 |= other.0; }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+    fn bitand(self, other: CardSet) -> CardSet { self.intersection(other) }
+}
+
+impl BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, other: CardSet) { self.0 &= other.0; }
+}
+
+impl BitXor for CardSet {
+    type Output = CardSet;
+    fn bitxor(self, other: CardSet) -> CardSet { self.symmetric_difference(other) }
+}
+
+impl BitXorAssign for CardSet {
+    fn bitxor_assign(&mut self, other: CardSet) { self.0 ^= other.0; }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+    fn sub(self, other: CardSet) -> CardSet { self.difference(other) }
+}
+
+impl SubAssign for CardSet {
+    fn sub_assign(&mut self, other: CardSet) { self.0 &= ! other.0; }
+}
+
+impl From<Card> for CardSet {
+    fn from(c: Card) -> CardSet {
+        CardSet::of_card(c)
+    }
+}
+
+impl<const N: usize> From<&SizedHand<N>> for CardSet {
+    fn from(h: &SizedHand<N>) -> CardSet {
+        h.cards[..h.len()].iter().copied().collect()
+    }
+}
+
+impl From<&Deck> for CardSet {
+    fn from(d: &Deck) -> CardSet {
+        d.iter().collect()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> CardSet {
+        CardSet(iter.into_iter().fold(0, |m, c| m | (1 << c.0)))
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for c in iter {
+            self.0 |= 1 << c.0;
+        }
+    }
+}
+
+impl std::fmt::Display for CardSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.iter() {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the [Card]s of a [CardSet]
+#[derive(Clone, Debug)]
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if 0 == self.0 { return None; }
+        let c = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(Card(c as u8))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.0.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for CardSetIter {
+    fn next_back(&mut self) -> Option<Card> {
+        if 0 == self.0 { return None; }
+        let c = 63 - self.0.leading_zeros();
+        self.0 &= ! (1 << c);
+        Some(Card(c as u8))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_card_set() -> Result<()> {
+        let mut deck = Deck::new(DeckType::TwoJokers);
+        let full = CardSet::from(&deck);
+        assert_eq!(full, CardSet::of_deck_type(DeckType::TwoJokers));
+        assert_eq!(full.len(), deck.len());
+        assert_eq!(full.of_rank(Rank::None).len(), 0);
+
+        for s in [Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade] {
+            let of = full.of_suit(s);
+            assert!(of.iter().all(|c| c.suit() == s));
+            assert_eq!(of.suit_mask(), 1 << s as u8);
+        }
+        for r in 1..=15 {
+            let r = Rank::from_u8(r);
+            let of = full.of_rank(r);
+            assert!(of.iter().all(|c| c.rank() == r));
+            assert_eq!(of.rank_mask(), if of.is_empty() { 0 } else { 1 << r as u8 });
+        }
+        for _ in 0..1000 {
+            deck.refill_and_shuffle();
+            let h = deck.new_hand().init(deck.draw(9));
+            let s = CardSet::from(&h);
+            assert_eq!(s.0, ojh_bitfield_64co(&h[..])?);
+            assert_eq!(s.len(), 9);
+            assert!(h.iter().all(|c| s.contains(c)));
+
+            let v: Vec<Card> = s.iter().collect();
+            assert!(v.windows(2).all(|w| w[0] < w[1]));
+            let mut r: Vec<Card> = s.iter().rev().collect();
+            r.reverse();
+            assert_eq!(v, r);
+            assert_eq!(s.to_hand(DeckType::TwoJokers).to_vec(), v);
+
+            let ranks = h.iter().filter(|c| ! c.is_joker())
+                .fold(0, |m, c| m | (1 << c.rank() as u32));
+            assert_eq!(s.rank_mask(), ranks);
+
+            let rest = CardSet::from(&deck);
+            assert!(s.is_disjoint(rest));
+            assert_eq!(s | rest, full);
+            assert_eq!(full - rest, s);
+            assert_eq!(full ^ s, rest);
+            assert_eq!(full & s, s);
+            assert!(s.is_subset(full));
+            assert_eq!(rest.to_deck(DeckType::TwoJokers).len(), deck.len());
+        }
+        let mut s = CardSet::new();
+        assert!(s.insert(card!("As")));
+        assert!(! s.insert(card!("As")));
+        s.extend(hand!("Kd", "Jk"));
+        assert_eq!(s.to_string(), "JkKdAs");
+        assert!(s.remove(card!("Kd")));
+        assert!(! s.remove(card!("Kd")));
+        assert_eq!(s.len(), 2);
+        Ok(())
+    }
+}
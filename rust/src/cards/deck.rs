@@ -372,6 +372,23 @@ impl Deck {
         false
     }
 
+    /// Remove every [Card] in the set from the [Deck]
+    ///
+    /// Return the number removed.
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let mut d = Deck::new(DeckType::English);
+    /// let dead: CardSet = hand!("As","Kd","Jk").into_iter().collect();
+    /// assert_eq!(2, d.remove_set(dead));
+    /// assert_eq!(50, d.len());
+    /// ```
+    pub fn remove_set(&mut self, set: CardSet) -> usize {
+        let before = self.cards.len();
+        self.cards.retain(|c| ! set.contains(*c));
+        before - self.cards.len()
+    }
+
     /// Synonym for `remove_card()`
     /// ```rust
     /// #[macro_use] extern crate onejoker;
@@ -534,7 +551,7 @@ impl IntoIterator for Deck {
     }
 }
 
-impl<'a> IntoIterator for &'a Deck {
+impl IntoIterator for &Deck {
     type Item = Card;
     type IntoIter = CardIter;
 
@@ -206,6 +206,16 @@ impl DeckType {
         0 != (DECK_INFO_TABLE[*self as usize - 1].card_set & (1 << c.0))
     }
 
+    /// Bitmask of the cards in the deck, as in [CardSet]
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// assert_eq!(DeckType::English.card_mask().count_ones(), 52);
+    /// ```
+    pub const fn card_mask(&self) -> u64 {
+        DECK_INFO_TABLE[*self as usize - 1].card_set
+    }
+
     /// Get a slice of the full deck
     /// ```rust
     /// use onejoker::prelude::*;
@@ -439,8 +449,8 @@ mod tests {
             }
             match dt.name() {
                 "allcards" => {
-                    assert_eq!(dt.dups_allowed(), false);
-                    assert_eq!(dt.low_aces(), false);
+                    assert!(! dt.dups_allowed());
+                    assert!(! dt.low_aces());
                     assert_eq!(dt.size(), 63);
                     assert!(dt.has(card!("Jk")));
                     assert!(dt.has(card!("Jb")));
@@ -451,8 +461,8 @@ mod tests {
                     assert!(dt.has(card!("1c")));
                 },
                 "english" => {
-                    assert_eq!(dt.dups_allowed(), false);
-                    assert_eq!(dt.low_aces(), false);
+                    assert!(! dt.dups_allowed());
+                    assert!(! dt.low_aces());
                     assert_eq!(dt.size(), 52);
                     assert!(! dt.has(card!("Jk")));
                     assert!(dt.has(card!("7c")));
@@ -460,8 +470,8 @@ mod tests {
                     assert!(dt.has(card!("Ac")));
                 },
                 "twojokers" => {
-                    assert_eq!(dt.dups_allowed(), false);
-                    assert_eq!(dt.low_aces(), false);
+                    assert!(! dt.dups_allowed());
+                    assert!(! dt.low_aces());
                     assert_eq!(dt.size(), 54);
                     assert!(dt.has(card!("Jb")));
                     assert!(dt.has(card!("Tc")));
@@ -470,8 +480,8 @@ mod tests {
                     assert!(! dt.has(card!("1c")));
                 },
                 "low" => {
-                    assert_eq!(dt.dups_allowed(), false);
-                    assert_eq!(dt.low_aces(), true);
+                    assert!(! dt.dups_allowed());
+                    assert!(dt.low_aces());
                     assert_eq!(dt.size(), 52);
                     assert!(! dt.has(JOKER));
                     assert!(dt.has(EIGHT_OF_CLUBS));
@@ -480,8 +490,8 @@ mod tests {
                     assert!(dt.has(LOW_ACE_OF_CLUBS));
                 },
                 "spanish" => {
-                    assert_eq!(dt.dups_allowed(), false);
-                    assert_eq!(dt.low_aces(), true);
+                    assert!(! dt.dups_allowed());
+                    assert!(dt.low_aces());
                     assert_eq!(dt.size(), 40);
                     assert!(! dt.has(JOKER));
                     assert!(! dt.has(EIGHT_OF_CLUBS));
@@ -490,8 +500,8 @@ mod tests {
                     assert!(! dt.has(QUEEN_OF_CLUBS));
                 },
                 "stripped" => {
-                    assert_eq!(dt.dups_allowed(), false);
-                    assert_eq!(dt.low_aces(), false);
+                    assert!(! dt.dups_allowed());
+                    assert!(! dt.low_aces());
                     assert_eq!(dt.size(), 32);
                     assert!(! dt.has(JOKER));
                     assert!(! dt.has(DEUCE_OF_CLUBS));
@@ -500,16 +510,16 @@ mod tests {
                     assert!(dt.has(ACE_OF_CLUBS));
                 },
                 "euchre" => {
-                    assert_eq!(dt.dups_allowed(), false);
-                    assert_eq!(dt.low_aces(), false);
+                    assert!(! dt.dups_allowed());
+                    assert!(! dt.low_aces());
                     assert_eq!(dt.size(), 24);
                     assert!(! dt.has(FIVE_OF_CLUBS));
                     assert!(dt.has(TEN_OF_CLUBS));
                     assert!(dt.has(ACE_OF_CLUBS));
                 },
                 "bezique" => {
-                    assert_eq!(dt.dups_allowed(), true);
-                    assert_eq!(dt.low_aces(), false);
+                    assert!(dt.dups_allowed());
+                    assert!(! dt.low_aces());
                     assert_eq!(dt.size(), 64);
                     assert!(! dt.has(JOKER));
                     assert!(! dt.has(SIX_OF_CLUBS));
@@ -517,8 +527,8 @@ mod tests {
                     assert!(dt.has(ACE_OF_CLUBS));
                 },
                 "pinochle" => {
-                    assert_eq!(dt.dups_allowed(), true);
-                    assert_eq!(dt.low_aces(), false);
+                    assert!(dt.dups_allowed());
+                    assert!(! dt.low_aces());
                     assert_eq!(dt.size(), 48);
                     assert!(! dt.has(JOKER));
                     assert!(! dt.has(EIGHT_OF_CLUBS));
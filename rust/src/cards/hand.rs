@@ -1,49 +1,85 @@
 //! [wiki](https://github.com/lcrocker/ojpoker/wiki/Hand) | Hand of cards
 
 use std::ops::{Index, IndexMut};
+use crate::error::{Error, Result};
 use crate::utils::*;
 use crate::cards::*;
 #[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
-const MAX_HAND_SIZE: usize = 22;
+pub(crate) const MAX_HAND_SIZE: usize = 22;
 
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/Hand) | Hand of cards
 ///
+/// The usual hand type, holding up to 22 cards, which is plenty for any
+/// poker game. See [SizedHand] for hands of other capacities.
+pub type Hand = SizedHand<MAX_HAND_SIZE>;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/SmallHand) | Hand of at most 8 cards
+///
+/// Big enough for a hold'em hand with the board, a stud hand, or omaha
+/// hole cards (but not omaha hole cards with the board, which take 9)
+/// while being cheap to copy.
+pub type SmallHand = SizedHand<8>;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/LargeHand) | Hand of at most 64 cards
+///
+/// For gin, canasta, and other games with large hands.
+pub type LargeHand = SizedHand<64>;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/SizedHand) | Hand of at most `N` cards
+///
 /// A simple array of card objects with some utility methods.
 /// It is expected that most access will go through `push()`/`pop()`, which
 /// are fast, though things like `insert()` and `remove()` are available.
-/// Limited to 22 cards. If you need more, you can use `Vec<Card>`, but you
-/// lose some error checking and convenience methods.
+/// Limited to `N` cards (at most 255); most code uses the [Hand] alias.
+/// If you need an unlimited number, you can use `Vec<Card>`, but you lose
+/// some error checking and convenience methods.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::cards::SmallHand;
+///
+/// let h = SmallHand::new(DeckType::English).init(hand!("Ah","Kh"));
+/// assert_eq!(h.capacity(), 8);
+/// assert!(std::mem::size_of::<SmallHand>() < std::mem::size_of::<Hand>());
+/// let big: Hand = h.resize().unwrap();
+/// assert_eq!(big.to_string(), "AhKh");
+/// ```
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(C)]
-pub struct Hand {
+pub struct SizedHand<const N: usize> {
     /// Array of [Card]s
-    pub cards: [Card; MAX_HAND_SIZE],
+    #[cfg_attr(feature = "serde", serde(with = "card_array"))]
+    pub cards: [Card; N],
     /// Number of cards in the hand
     pub length: u8,
     /// [DeckType] associated with this hand
     pub deck_type: DeckType
 }
 
-impl Hand {
+impl<const N: usize> SizedHand<N> {
+    // Lengths are kept in a u8
+    const CAPACITY_OK: () = assert!(N <= 255, "hand capacity too large");
+
     /// Create new [Hand] with the given [DeckType]
     /// ```rust
     /// use onejoker::prelude::*;
     ///
     /// let h = Hand::new(DeckType::OneJoker);
     /// ```
-    pub fn new(deck_type: DeckType) -> Hand {
-        Hand {
-            cards: [Card::default(); MAX_HAND_SIZE],
+    pub fn new(deck_type: DeckType) -> Self {
+        let () = Self::CAPACITY_OK;
+
+        SizedHand {
+            cards: [Card::default(); N],
             length: 0,
             deck_type,
         }
     }
 
     /// Clone the hand, change its deck type, and fix the aces
-    pub fn convert_decktype(&self, t: DeckType) -> Hand {
+    pub fn convert_decktype(&self, t: DeckType) -> Self {
         let mut h = *self;
         h.deck_type = t;
         for i in 0..h.len() {
@@ -58,12 +94,41 @@ impl Hand {
     ///
     /// let h = Hand::new_by_name("onejoker");
     /// ```
-    pub fn new_by_name(dname: &str) -> Hand {
-        Hand {
-            cards: [Card::default(); MAX_HAND_SIZE],
-            length: 0,
-            deck_type: DeckType::by_name(dname),
+    pub fn new_by_name(dname: &str) -> Self {
+        Self::new(DeckType::by_name(dname))
+    }
+
+    /// Maximum number of cards the hand can hold
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// assert_eq!(Hand::default().capacity(), 22);
+    /// ```
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Copy of the hand with a different capacity
+    ///
+    /// Fails if the cards won't fit.
+    /// ```rust
+    /// use onejoker::prelude::*;
+    /// use onejoker::cards::{SmallHand, LargeHand};
+    ///
+    /// let h = Hand::default().init(hand!("Ah","Kh","Qh","Jh","Th","9h","8h","7h","6h"));
+    /// assert!(h.resize::<8>().is_err());
+    /// let big: LargeHand = h.resize().unwrap();
+    /// assert_eq!(big.len(), 9);
+    /// ```
+    pub fn resize<const M: usize>(&self) -> Result<SizedHand<M>> {
+        if self.len() > M {
+            return Err(Error::BadHand(format!("{} cards won't fit in hand of {}",
+                self.len(), M)));
         }
+        let mut h = SizedHand::<M>::new(self.deck_type);
+        h.cards[..self.len()].copy_from_slice(&self.cards[..self.len()]);
+        h.length = self.length;
+        Ok(h)
     }
 
     /// Initialize new hand
@@ -252,7 +317,7 @@ impl Hand {
     /// assert_eq!(h.to_string(), "AhKhQh");
     /// ```
     pub fn push(&mut self, card: Card) -> bool {
-        if (self.length as usize) >= MAX_HAND_SIZE {
+        if (self.length as usize) >= N {
             return false;
         }
         let c = self.deck_type().valid_card(card);
@@ -292,7 +357,7 @@ impl Hand {
         let mut pushed: usize = 0;
 
         for c in iter {
-            if (self.length as usize) >= MAX_HAND_SIZE {
+            if (self.length as usize) >= N {
                 break;
             }
             let cout = self.deck_type().valid_card(c);
@@ -321,7 +386,7 @@ impl Hand {
         let mut pushed: usize = 0;
 
         for c in iter {
-            if (self.length as usize) >= MAX_HAND_SIZE {
+            if (self.length as usize) >= N {
                 break;
             }
             let cout = self.deck_type().valid_card(c);
@@ -391,7 +456,7 @@ impl Hand {
     /// ```
     pub fn insert_at(&mut self, index: usize, card: Card) -> bool {
         if index <= (self.length as usize) &&
-            (self.length as usize) < MAX_HAND_SIZE {
+            (self.length as usize) < N {
 
             let c = self.deck_type().valid_card(card);
             for i in (index..(self.length as usize)).rev() {
@@ -493,7 +558,7 @@ impl Hand {
     /// }
     /// assert_eq!(count, 10);
     /// ```
-    pub fn combinations(&self, k: usize) -> impl Iterator<Item = Hand> {
+    pub fn combinations(&self, k: usize) -> impl Iterator<Item = Self> {
         CardCombinationIter::new(self, k)
     }
 
@@ -536,8 +601,8 @@ impl Hand {
             return false;
         }
         if self.deck_type().dups_allowed() {
-            let mut ss: Hand = *self;
-            let mut os: Hand = *other;
+            let mut ss = *self;
+            let mut os = *other;
             oj_sort(&mut ss.cards[..(ss.length as usize)]);
             oj_sort(&mut os.cards[..(os.length as usize)]);
 
@@ -691,7 +756,7 @@ impl Hand {
     }
 }
 
-impl Index<usize> for Hand {
+impl<const N: usize> Index<usize> for SizedHand<N> {
     type Output = Card;
     #[inline]
     fn index(&self, index: usize) -> &Self::Output {
@@ -699,14 +764,14 @@ impl Index<usize> for Hand {
     }
 }
 
-impl IndexMut<usize> for Hand {
+impl<const N: usize> IndexMut<usize> for SizedHand<N> {
     #[inline]
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.cards[index]
     }
 }
 
-impl Index<std::ops::Range<usize>> for Hand {
+impl<const N: usize> Index<std::ops::Range<usize>> for SizedHand<N> {
     type Output = [Card];
 
     #[inline]
@@ -715,14 +780,14 @@ impl Index<std::ops::Range<usize>> for Hand {
     }
 }
 
-impl IndexMut<std::ops::Range<usize>> for Hand {
+impl<const N: usize> IndexMut<std::ops::Range<usize>> for SizedHand<N> {
     #[inline]
     fn index_mut(&mut self, index: std::ops::Range<usize>) -> &mut Self::Output {
         &mut self.cards[index]
     }
 }
 
-impl Index<std::ops::RangeFrom<usize>> for Hand {
+impl<const N: usize> Index<std::ops::RangeFrom<usize>> for SizedHand<N> {
     type Output = [Card];
 
     #[inline]
@@ -731,14 +796,14 @@ impl Index<std::ops::RangeFrom<usize>> for Hand {
     }
 }
 
-impl IndexMut<std::ops::RangeFrom<usize>> for Hand {
+impl<const N: usize> IndexMut<std::ops::RangeFrom<usize>> for SizedHand<N> {
     #[inline]
     fn index_mut(&mut self, index: std::ops::RangeFrom<usize>) -> &mut Self::Output {
         &mut self.cards[index.start..self.length as usize]
     }
 }
 
-impl Index<std::ops::RangeTo<usize>> for Hand {
+impl<const N: usize> Index<std::ops::RangeTo<usize>> for SizedHand<N> {
     type Output = [Card];
 
     #[inline]
@@ -747,14 +812,14 @@ impl Index<std::ops::RangeTo<usize>> for Hand {
     }
 }
 
-impl IndexMut<std::ops::RangeTo<usize>> for Hand {
+impl<const N: usize> IndexMut<std::ops::RangeTo<usize>> for SizedHand<N> {
     #[inline]
     fn index_mut(&mut self, index: std::ops::RangeTo<usize>) -> &mut Self::Output {
         &mut self.cards[index]
     }
 }
 
-impl Index<std::ops::RangeFull> for Hand {
+impl<const N: usize> Index<std::ops::RangeFull> for SizedHand<N> {
     type Output = [Card];
 
     #[inline]
@@ -763,14 +828,14 @@ impl Index<std::ops::RangeFull> for Hand {
     }
 }
 
-impl IndexMut<std::ops::RangeFull> for Hand {
+impl<const N: usize> IndexMut<std::ops::RangeFull> for SizedHand<N> {
     #[inline]
     fn index_mut(&mut self, _index: std::ops::RangeFull) -> &mut Self::Output {
         &mut self.cards[..self.length as usize]
     }
 }
 
-impl std::fmt::Display for Hand {
+impl<const N: usize> std::fmt::Display for SizedHand<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut s: String = "".to_string();
         if !self.is_empty() {
@@ -784,7 +849,7 @@ impl std::fmt::Display for Hand {
     }
 }
 
-impl std::fmt::Debug for Hand {
+impl<const N: usize> std::fmt::Debug for SizedHand<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut s: String = "".to_string();
 
@@ -799,13 +864,13 @@ impl std::fmt::Debug for Hand {
     }
 }
 
-impl std::default::Default for Hand {
+impl<const N: usize> std::default::Default for SizedHand<N> {
     fn default() -> Self {
-        Hand::new(DeckType::default())
+        Self::new(DeckType::default())
     }
 }
 
-impl std::hash::Hash for Hand {
+impl<const N: usize> std::hash::Hash for SizedHand<N> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         for i in 0..(self.length as usize) {
             self.cards[i].hash(state);
@@ -813,14 +878,14 @@ impl std::hash::Hash for Hand {
     }
 }
 
-impl std::cmp::PartialEq for Hand {
+impl<const N: usize> std::cmp::PartialEq for SizedHand<N> {
     fn eq(&self, other: &Self) -> bool {
         self.equals(other)
     }
 }
-impl std::cmp::Eq for Hand {}
+impl<const N: usize> std::cmp::Eq for SizedHand<N> {}
 
-impl std::cmp::Ord for Hand {
+impl<const N: usize> std::cmp::Ord for SizedHand<N> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match self.length.cmp(&other.length) {
             std::cmp::Ordering::Less => std::cmp::Ordering::Less,
@@ -844,7 +909,7 @@ impl std::cmp::Ord for Hand {
     }
 }
 
-impl std::cmp::PartialOrd for Hand {
+impl<const N: usize> std::cmp::PartialOrd for SizedHand<N> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
@@ -875,7 +940,7 @@ impl Iterator for CardIter {
     }
 }
 
-impl Hand {
+impl<const N: usize> SizedHand<N> {
     /// Return an iterator over the [Card]s in the hand.
     pub fn iter(&self) -> CardIter {
         CardIter::new(self.to_vec())
@@ -909,7 +974,7 @@ impl Iterator for CardIntoIter {
     }
 }
 
-impl IntoIterator for Hand {
+impl<const N: usize> IntoIterator for SizedHand<N> {
     type Item = Card;
     type IntoIter = CardIntoIter;
 
@@ -918,7 +983,7 @@ impl IntoIterator for Hand {
     }
 }
 
-impl<'a> IntoIterator for &'a Hand {
+impl<const N: usize> IntoIterator for &SizedHand<N> {
     type Item = Card;
     type IntoIter = CardIter;
 
@@ -927,17 +992,17 @@ impl<'a> IntoIterator for &'a Hand {
     }
 }
 
-struct CardCombinationIter {
+struct CardCombinationIter<const N: usize> {
     source: Vec<Card>,
-    dest: Hand,
+    dest: SizedHand<N>,
     indices: Vec<usize>,
     done: bool,
 }
 
-impl CardCombinationIter {
-    pub fn new(hand: &Hand, k: usize) -> CardCombinationIter {
+impl<const N: usize> CardCombinationIter<N> {
+    pub fn new(hand: &SizedHand<N>, k: usize) -> Self {
         let source = hand.to_vec();
-        let mut dest: Hand = *hand;
+        let mut dest = *hand;
         dest.truncate(k);
 
         let mut indices: Vec<usize> = Vec::with_capacity(k);
@@ -948,8 +1013,8 @@ impl CardCombinationIter {
     }
 }
 
-impl Iterator for CardCombinationIter {
-    type Item = Hand;
+impl<const N: usize> Iterator for CardCombinationIter<N> {
+    type Item = SizedHand<N>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
@@ -963,6 +1028,55 @@ impl Iterator for CardCombinationIter {
     }
 }
 
+// Serde only handles arrays of fixed small sizes, so write the cards as a
+// tuple of `N` the same way it does those.
+#[cfg(feature = "serde")]
+mod card_array {
+    use serde::{Serializer, Deserializer};
+    use serde::ser::SerializeTuple;
+    use serde::de::{Visitor, SeqAccess, Error};
+    use crate::cards::Card;
+
+    pub fn serialize<S, const N: usize>(cards: &[Card; N], s: S)
+    -> std::result::Result<S::Ok, S::Error>
+    where S: Serializer {
+        let mut t = s.serialize_tuple(N)?;
+        for c in cards {
+            t.serialize_element(c)?;
+        }
+        t.end()
+    }
+
+    struct CardArrayVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for CardArrayVisitor<N> {
+        type Value = [Card; N];
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "an array of {} cards", N)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> std::result::Result<[Card; N], A::Error>
+        where A: SeqAccess<'de> {
+            let mut cards = [Card::default(); N];
+            for (i, c) in cards.iter_mut().enumerate() {
+                *c = seq.next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<Card>()?.is_some() {
+                return Err(A::Error::invalid_length(N + 1, &self));
+            }
+            Ok(cards)
+        }
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(d: D)
+    -> std::result::Result<[Card; N], D::Error>
+    where D: Deserializer<'de> {
+        d.deserialize_tuple(N, CardArrayVisitor::<N>)
+    }
+}
+
 /*
  * CODE ENDS HERE
  */
@@ -1069,7 +1183,7 @@ mod tests {
         assert_eq!(h.to_string(), "4sJkJcQd9dAc6s");
         assert_eq!(h.remove_at(2).unwrap(), JACK_OF_CLUBS);
         assert_eq!(h.to_string(), "4sJkQd9dAc6s");
-        assert_eq!(h.remove_card(ACE_OF_CLUBS), true);
+        assert!(h.remove_card(ACE_OF_CLUBS));
         assert_eq!(h.to_string(), "4sJkQd9d6s");
         assert_eq!(h.pop().unwrap(), SIX_OF_SPADES);
         assert_eq!(h.to_string(), "4sJkQd9d");
@@ -1102,4 +1216,40 @@ mod tests {
         assert_eq!(h.to_string(), "QsTh8d7s5h4h3h3cJk");
         Ok(())
     }
+
+    #[test]
+    fn test_hand_capacity() -> Result<()> {
+        has_traits::<SmallHand>();
+        has_traits::<LargeHand>();
+
+        let mut d = Deck::new(DeckType::Canasta);
+        let mut small = SmallHand::new(DeckType::Canasta);
+        assert_eq!(8, small.push_all(d.pop_n(10)));
+        assert!(! small.push(JOKER));
+        assert!(! small.insert_at(0, JOKER));
+        assert_eq!(small.len(), 8);
+
+        let mut large = LargeHand::new(DeckType::Canasta);
+        assert_eq!(60, large.push_all(d.pop_n(60)));
+        assert_eq!(large.combinations(59).count(), 60);
+        assert!(large.resize::<MAX_HAND_SIZE>().is_err());
+
+        let h: Hand = small.resize()?;
+        assert_eq!(h.capacity(), MAX_HAND_SIZE);
+        assert_eq!(h.to_string(), small.to_string());
+        let back: SmallHand = h.resize()?;
+        assert_eq!(back, small);
+
+        #[cfg(feature = "serde")]
+        {
+            let text = serde_json5::to_string(&large)?;
+            let l2: LargeHand = serde_json5::from_str(&text)?;
+            assert_eq!(l2, large);
+            assert_eq!(l2.deck_type(), DeckType::Canasta);
+
+            let text = serde_json5::to_string(&h)?;
+            assert!(serde_json5::from_str::<SmallHand>(&text).is_err());
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,471 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/HandIndexer) | Perfect index of suit-isomorphic hands
+//!
+//! Maps cards dealt in rounds (like 2 hole cards, then 3, 1, and 1 board
+//! cards in hold'em) to a dense index, such that two deals get the same
+//! index exactly when they differ only by a renaming of suits. This is the
+//! approach of Waugh's "A Fast and Optimal Hand Isomorphism Algorithm":
+//! each suit's cards are indexed on their own, suits holding the same
+//! number of cards in each round are interchangeable, so their indices are
+//! combined as a multiset, and all of that is offset by the index of the
+//! overall pattern of suit counts. Jokers are not renamed by suit changes,
+//! so they are indexed separately.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::error::{Error, Result};
+use crate::cards::*;
+use crate::utils::oj_binomial;
+
+// Suits assigned to the canonical order of suits when unranking
+const UNRANK_SUITS: [Suit; 4] = [ Suit::Spade, Suit::Heart, Suit::Diamond,
+    Suit::Club ];
+
+// Binomial coefficient for any size, saturating on overflow
+fn choose(n: u64, k: u64) -> u128 {
+    if k > n { return 0; }
+    if n < 64 { return oj_binomial(n as i32, k as i32) as u128; }
+
+    let k = k.min(n - k);
+    let mut r: u128 = 1;
+    for i in 0..k {
+        let Some(x) = r.checked_mul((n - i) as u128) else {
+            return u128::MAX;
+        };
+        r = x / (i + 1) as u128;
+    }
+    r
+}
+
+// Number of ways to fill `counts[r]` of the `slots` in each round
+fn component_size(slots: usize, counts: &[u8]) -> u64 {
+    let mut used = 0;
+    let mut size: u64 = 1;
+
+    for &c in counts {
+        size *= oj_binomial((slots - used) as i32, c as i32);
+        used += c as usize;
+    }
+    size
+}
+
+// Index of the slots filled in each round: colex within each round over
+// the slots not already filled, mixed radix across rounds
+fn component_index(slots: usize, masks: &[u64]) -> u64 {
+    let mut used: u64 = 0;
+    let mut index: u64 = 0;
+
+    for &m in masks {
+        let avail = slots - used.count_ones() as usize;
+        let mut sub: u64 = 0;
+        let mut i = 0;
+
+        for p in 0..slots {
+            if 0 == m & (1 << p) { continue; }
+            let rel = p - (used & ((1 << p) - 1)).count_ones() as usize;
+            i += 1;
+            sub += oj_binomial(rel as i32, i);
+        }
+        index = index * oj_binomial(avail as i32, i) + sub;
+        used |= m;
+    }
+    index
+}
+
+fn component_unrank(slots: usize, counts: &[u8], mut index: u64) -> Vec<u64> {
+    let mut subs = vec![0; counts.len()];
+    let mut avail = slots - counts.iter().map(|&c| c as usize).sum::<usize>();
+
+    for (r, &c) in counts.iter().enumerate().rev() {
+        avail += c as usize;
+        let radix = oj_binomial(avail as i32, c as i32);
+        subs[r] = index % radix;
+        index /= radix;
+    }
+    let mut used: u64 = 0;
+    let mut masks = Vec::with_capacity(counts.len());
+
+    for (r, &c) in counts.iter().enumerate() {
+        let mut sub = subs[r];
+        let mut m: u64 = 0;
+        let mut top = slots - used.count_ones() as usize;
+
+        for i in (1..=(c as i32)).rev() {
+            let p = (0..top).rev().find(|&p| oj_binomial(p as i32, i) <= sub)
+                .expect("valid component index");
+            sub -= oj_binomial(p as i32, i);
+            top = p;
+
+            let slot = (0..slots).filter(|&s| 0 == used & (1 << s))
+                .nth(p).expect("free slot");
+            m |= 1 << slot;
+        }
+        masks.push(m);
+        used |= m;
+    }
+    masks
+}
+
+// Index of a multiset of `a.len()` values below `size`, given descending
+fn multiset_index(a: &[u64]) -> u128 {
+    let m = a.len() as u64;
+    a.iter().enumerate().map(|(j, &x)| {
+        let j = j as u64;
+        choose(x + (m - 1 - j), m - j)
+    }).sum()
+}
+
+fn multiset_unrank(size: u64, m: usize, mut index: u128) -> Vec<u64> {
+    let m = m as u64;
+    let mut out = Vec::with_capacity(m as usize);
+    let mut hi = size + m - 1;
+
+    for j in 0..m {
+        let k = m - j;
+        // largest b < hi with choose(b, k) <= index
+        let (mut lo, mut top) = (0u64, hi);
+        while top - lo > 1 {
+            let mid = lo + (top - lo) / 2;
+            if choose(mid, k) <= index { lo = mid; } else { top = mid; }
+        }
+        index -= choose(lo, k);
+        out.push(lo - (m - 1 - j));
+        hi = lo;
+    }
+    out
+}
+
+// One pattern of card counts: per round for the jokers, and per round
+// for each suit, with suits in descending order
+#[derive(Debug, Clone)]
+struct Config {
+    jokers: Vec<u8>,
+    suits: [Vec<u8>; 4],
+    joker_size: u64,
+    groups: Vec<(usize, usize, u64, u64)>,  // start, length, suit size, size
+    offset: u64,
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/HandIndexer) | Perfect index of suit-isomorphic hands
+///
+/// Built for a deck type and the number of cards dealt in each round.
+/// `index` maps the rounds of a deal to a number in `0..size()`, equal
+/// for deals that are suit-isomorphic and different otherwise, and
+/// `unrank` returns one deal for each index.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::cards::HandIndexer;
+///
+/// let flop = HandIndexer::new(DeckType::English, &[2, 3]).unwrap();
+/// assert_eq!(flop.size(), 1_286_792);
+///
+/// let hole = Hand::new(DeckType::English).init(hand!("Ah","Kh"));
+/// let board = Hand::new(DeckType::English).init(hand!("7h","7c","2d"));
+/// let i = flop.index(&[hole, board]).unwrap();
+///
+/// let hole2 = Hand::new(DeckType::English).init(hand!("Kd","Ad"));
+/// let board2 = Hand::new(DeckType::English).init(hand!("2s","7d","7c"));
+/// assert_eq!(i, flop.index(&[hole2, board2]).unwrap());
+///
+/// let deal = flop.unrank(i).unwrap();
+/// assert_eq!(i, flop.index(&deal).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct HandIndexer {
+    deck: DeckType,
+    rounds: Vec<usize>,
+    ranks: Vec<Rank>,
+    jokers: Vec<Card>,
+    configs: Vec<Config>,
+    lookup: HashMap<Vec<u8>, usize>,
+    size: u64,
+}
+
+impl HandIndexer {
+    /// New indexer for the deck type and number of cards in each round
+    pub fn new(deck: DeckType, rounds: &[usize]) -> Result<HandIndexer> {
+        if deck.dups_allowed() {
+            return Err(Error::HashDomain(format!("{} deck has duplicate \
+                cards", deck.name())));
+        }
+        let cards = deck.card_list();
+        let mut ranks: Vec<Rank> = cards.iter()
+            .filter(|c| Suit::None != c.suit()).map(|c| c.rank()).collect();
+        ranks.sort();
+        ranks.dedup();
+        let jokers: Vec<Card> = cards.iter()
+            .filter(|c| Suit::None == c.suit()).copied().collect();
+
+        let total: usize = rounds.iter().sum();
+        if rounds.is_empty() || total > MAX_HAND_SIZE || total > cards.len() {
+            return Err(Error::HashDomain(format!("{:?} cards from {} deck",
+                rounds, deck.name())));
+        }
+        let mut patterns: BTreeSet<Vec<Vec<u8>>> = BTreeSet::new();
+        let mut counts = vec![Vec::new(); 5];
+        distribute(rounds, ranks.len(), jokers.len(), &mut counts, &mut patterns);
+
+        let mut configs = Vec::new();
+        let mut lookup = HashMap::new();
+        let mut offset: u64 = 0;
+
+        for p in patterns.into_iter() {
+            let suits = [p[1].clone(), p[2].clone(), p[3].clone(), p[4].clone()];
+            let joker_size = component_size(jokers.len(), &p[0]);
+            let mut groups = Vec::new();
+            let mut size: u128 = joker_size as u128;
+
+            let mut start = 0;
+            while start < 4 {
+                let len = (start..4).take_while(|&i| suits[i] == suits[start])
+                    .count();
+                let each = component_size(ranks.len(), &suits[start]);
+                let gsize = choose(each + len as u64 - 1, len as u64);
+                size = size.saturating_mul(gsize);
+                groups.push((start, len, each, gsize as u64));
+                start += len;
+            }
+            let Some(next) = u64::try_from(size).ok()
+                .and_then(|s| offset.checked_add(s)) else {
+                return Err(Error::HashDomain("index too large".into()));
+            };
+            lookup.insert(p.concat(), configs.len());
+            configs.push(Config { jokers: p[0].clone(), suits, joker_size,
+                groups, offset });
+            offset = next;
+        }
+        Ok(HandIndexer { deck, rounds: rounds.to_vec(), ranks, jokers,
+            configs, lookup, size: offset })
+    }
+
+    /// Number of distinct indices
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Number of cards in each round
+    pub fn rounds(&self) -> &[usize] {
+        &self.rounds
+    }
+
+    /// Deck type of the cards
+    pub fn deck_type(&self) -> DeckType {
+        self.deck
+    }
+
+    /// Index of the cards dealt in each round
+    pub fn index(&self, rounds: &[Hand]) -> Result<u64> {
+        let n = self.rounds.len();
+        if rounds.len() != n ||
+            rounds.iter().zip(self.rounds.iter()).any(|(h, &k)| h.len() != k) {
+            return Err(Error::HashDomain(format!("expecting {:?} cards",
+                self.rounds)));
+        }
+        let mut suit_masks = [vec![0u64; n], vec![0u64; n], vec![0u64; n],
+            vec![0u64; n]];
+        let mut joker_masks = vec![0u64; n];
+        let mut seen: u64 = 0;
+
+        for (r, h) in rounds.iter().enumerate() {
+            for c in h.iter() {
+                let c = self.deck.fix_ace(c);
+                if ! self.deck.has(c) {
+                    return Err(Error::InvalidCard(c, self.deck.name().into()));
+                }
+                if 0 != seen & (1 << c.0) {
+                    return Err(Error::DuplicateCard(c));
+                }
+                seen |= 1 << c.0;
+
+                if Suit::None == c.suit() {
+                    let p = self.jokers.iter().position(|&j| j == c)
+                        .expect("joker in deck");
+                    joker_masks[r] |= 1 << p;
+                } else {
+                    let p = self.ranks.iter().position(|&x| x == c.rank())
+                        .expect("rank in deck");
+                    suit_masks[c.suit() as usize - 1][r] |= 1 << p;
+                }
+            }
+        }
+        let counts = |masks: &[u64]| -> Vec<u8> {
+            masks.iter().map(|m| m.count_ones() as u8).collect()
+        };
+        let mut suits: Vec<(Vec<u8>, u64)> = suit_masks.iter().map(|m|
+            (counts(m), component_index(self.ranks.len(), m))).collect();
+        suits.sort_by(|a, b| b.cmp(a));
+
+        let mut key = counts(&joker_masks);
+        for (c, _) in suits.iter() {
+            key.extend_from_slice(c);
+        }
+        let cfg = &self.configs[self.lookup[&key]];
+        let mut index = component_index(self.jokers.len(), &joker_masks);
+
+        for &(start, len, _, gsize) in cfg.groups.iter() {
+            let a: Vec<u64> = suits[start..(start + len)].iter()
+                .map(|s| s.1).collect();
+            index = index * gsize + multiset_index(&a) as u64;
+        }
+        Ok(cfg.offset + index)
+    }
+
+    /// One deal of cards in rounds, each sorted, with the given index
+    pub fn unrank(&self, index: u64) -> Result<Vec<Hand>> {
+        if index >= self.size {
+            return Err(Error::HashDomain(format!("index {} out of range",
+                index)));
+        }
+        let ci = self.configs.partition_point(|c| c.offset <= index) - 1;
+        let cfg = &self.configs[ci];
+        let mut rem = index - cfg.offset;
+        let mut suit_index = [0u64; 4];
+
+        for &(start, len, each, gsize) in cfg.groups.iter().rev() {
+            let a = multiset_unrank(each, len, (rem % gsize) as u128);
+            suit_index[start..(start + len)].copy_from_slice(&a);
+            rem /= gsize;
+        }
+        debug_assert!(rem < cfg.joker_size.max(1));
+
+        let mut hands = vec![Hand::new(self.deck); self.rounds.len()];
+        let jm = component_unrank(self.jokers.len(), &cfg.jokers, rem);
+        for (r, m) in jm.iter().enumerate() {
+            hands[r].push_all(self.jokers.iter().enumerate()
+                .filter(|(p, _)| 0 != m & (1 << p)).map(|(_, &j)| j));
+        }
+        for s in 0..4 {
+            let sm = component_unrank(self.ranks.len(), &cfg.suits[s],
+                suit_index[s]);
+            for (r, m) in sm.iter().enumerate() {
+                hands[r].push_all(self.ranks.iter().enumerate()
+                    .filter(|(p, _)| 0 != m & (1 << p))
+                    .map(|(_, &rk)| Card::from_rank_suit(rk, UNRANK_SUITS[s])));
+            }
+        }
+        Ok(hands.into_iter().map(|h| h.sorted()).collect())
+    }
+}
+
+// Every way to split each round's cards among jokers and the four suits,
+// with suits sorted so that renamings of suits are only counted once
+fn distribute(rounds: &[usize], ranks: usize, jokers: usize,
+counts: &mut Vec<Vec<u8>>, out: &mut BTreeSet<Vec<Vec<u8>>>) {
+    let r = counts[0].len();
+    if r == rounds.len() {
+        let mut p = counts.clone();
+        p[1..].sort_by(|a, b| b.cmp(a));
+        out.insert(p);
+        return;
+    }
+    let cap: Vec<usize> = counts.iter().enumerate().map(|(i, c)| {
+        let used: usize = c.iter().map(|&x| x as usize).sum();
+        if 0 == i { jokers - used } else { ranks - used }
+    }).collect();
+
+    let mut split = [0usize; 5];
+    loop {
+        if split.iter().sum::<usize>() == rounds[r] {
+            for (i, c) in counts.iter_mut().enumerate() {
+                c.push(split[i] as u8);
+            }
+            distribute(rounds, ranks, jokers, counts, out);
+            for c in counts.iter_mut() {
+                c.pop();
+            }
+        }
+        // next split, odometer style, each digit up to its capacity
+        let mut i = 0;
+        while i < 5 {
+            if split[i] < cap[i].min(rounds[r]) {
+                split[i] += 1;
+                break;
+            }
+            split[i] = 0;
+            i += 1;
+        }
+        if 5 == i { break; }
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::utils::Random;
+
+    #[test]
+    fn test_hand_indexer() -> Result<()> {
+        let english = DeckType::English;
+        for (rounds, size) in [(vec![2], 169), (vec![3], 1755),
+            (vec![5], 134_459), (vec![2, 3], 1_286_792),
+            (vec![2, 3, 1], 55_190_538), (vec![2, 3, 1, 1], 2_428_287_420)] {
+            assert_eq!(HandIndexer::new(english, &rounds)?.size(), size);
+        }
+
+        // Same classes as the canonicalizer, for several decks
+        for (dt, k) in [(english, 2), (english, 3), (DeckType::TwoJokers, 2),
+            (DeckType::Stripped, 3), (DeckType::Low, 2)] {
+            let ix = HandIndexer::new(dt, &[k])?;
+            let mut classes: HashMap<Vec<Hand>, u64> = HashMap::new();
+
+            for h in Deck::new(dt).combinations(k) {
+                let i = ix.index(&[h])?;
+                let c = ojc_canonical(&h).hands;
+                assert_eq!(*classes.entry(c).or_insert(i), i);
+            }
+            assert_eq!(classes.len() as u64, ix.size());
+            for i in 0..ix.size() {
+                assert_eq!(ix.index(&ix.unrank(i)?)?, i);
+            }
+        }
+
+        // Multiple rounds: renamed suits match, rounds matter
+        let mut rng = Random::new().seeded(2024);
+        let river = HandIndexer::new(english, &[2, 3, 1, 1])?;
+        let mut deck = Deck::new(english);
+        for _ in 0..1000 {
+            deck.refill_and_shuffle();
+            let deal: Vec<Hand> = river.rounds().iter().map(|&k| {
+                let cards: Vec<Card> = deck.draw(k).collect();
+                Hand::new(english).init(cards)
+            }).collect();
+            let i = river.index(&deal)?;
+
+            let p = SuitPermutation::all().nth(rng.uniform16(24)).unwrap();
+            let renamed: Vec<Hand> = deal.iter().map(|h| p.apply_hand(h)).collect();
+            assert_eq!(river.index(&renamed)?, i);
+
+            let u = river.unrank(i)?;
+            assert_eq!(ojc_canonical_rounds(&u).hands,
+                ojc_canonical_rounds(&deal).hands);
+
+            let j = rng.next64() % river.size();
+            assert_eq!(river.index(&river.unrank(j)?)?, j);
+        }
+        let flop = HandIndexer::new(english, &[2, 3])?;
+        let h1 = [Hand::new(english).init(hand!("Ah","Kh")),
+            Hand::new(english).init(hand!("Qh","2c","3d"))];
+        let h2 = [Hand::new(english).init(hand!("Ah","Qh")),
+            Hand::new(english).init(hand!("Kh","2c","3d"))];
+        assert_ne!(flop.index(&h1)?, flop.index(&h2)?);
+        assert_eq!(flop.index(&flop.unrank(0)?)?, 0);
+        assert_eq!(flop.index(&flop.unrank(flop.size() - 1)?)?, flop.size() - 1);
+
+        // Errors
+        assert!(flop.unrank(flop.size()).is_err());
+        assert!(flop.index(&h1[..1]).is_err());
+        let dup = [Hand::new(english).init(hand!("Ah","Kh")),
+            Hand::new(english).init(hand!("Ah","2c","3d"))];
+        assert!(flop.index(&dup).is_err());
+        let joker = [Hand::new(english).init(hand!("Ah","Kh")),
+            Hand::new(DeckType::OneJoker).init(hand!("Jk","2c","3d"))];
+        assert!(flop.index(&joker).is_err());
+        assert!(HandIndexer::new(DeckType::Canasta, &[2]).is_err());
+        assert!(HandIndexer::new(english, &[20, 20, 20]).is_err());
+        Ok(())
+    }
+}
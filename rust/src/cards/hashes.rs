@@ -787,6 +787,39 @@ pub fn ojh_bitfield_mp5_low(cards: &[Card]) -> u32 {
     h as u32
 }
 
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Perfect_Hash) | Convert bitfield to MPH
+/// Given a bitfield with exactly 3 bits set, return the lexicographic
+/// index of that particular set of bits for minimal perfect hash.
+/// High-ace deck version, 3 cards (for three-card poker).
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::cards::hashes::*;
+///
+/// let d = Deck::new(DeckType::English);
+/// let h = d.new_hand().init(hand!("2c","2d","2h"));
+/// assert_eq!(1, ojh_bitfield_mp3_english(&h[..]));
+/// ```
+pub fn ojh_bitfield_mp3_english(cards: &[Card]) -> u32 {
+    let mut bf = ojh_bitfield_64co(cards).unwrap();
+    bf >>= 8;
+    bf = (bf & 0x00FF_FFFF_FFFF) | ((bf & 0x00FF_F000_0000_0000) >> 4);
+
+    let mut h: u64 = oj_binomial(52, 3);
+    let mut mask = 0x0008_0000_0000_0000;
+    let mut m = 1;
+
+    for j in 0..52 {
+        if 0 != (bf & mask) {
+            h -= oj_binomial(j, m);
+            m += 1;
+            if m > 3 { break; }
+        }
+        mask >>= 1;
+    }
+    debug_assert!(h <= 22100);
+    h as u32
+}
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/Perfect_Hash) | Convert bitfield to MPH
 /// Given a bitfield with exactly 4 bits set, return the lexicographic
 /// index of that particular set of bits for minimal perfect hash.
@@ -0,0 +1,255 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Isomorphism) | Suit-isomorphic canonical hands
+//!
+//! Most games treat the four suits alike, so hands that differ only by a
+//! renaming of suits (like "AhKh" and "AsKs") have the same value, equity,
+//! and so on. Here we reduce a hand, or a series of hands dealt in rounds
+//! like hole cards and board, to a single canonical member of its class.
+
+use crate::cards::*;
+
+const ALL_SUITS: [Suit; 4] = [ Suit::Club, Suit::Diamond, Suit::Heart,
+    Suit::Spade ];
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/SuitPermutation) | A renaming of the four suits
+///
+/// Jokers and other suitless cards are left alone.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::cards::SuitPermutation;
+///
+/// let p = SuitPermutation::new([Suit::Heart, Suit::Club, Suit::Spade, Suit::Diamond]);
+/// assert_eq!(p.apply(ACE_OF_CLUBS), ACE_OF_HEARTS);
+/// assert_eq!(p.inverse().apply(ACE_OF_HEARTS), ACE_OF_CLUBS);
+/// assert_eq!(p.apply(JOKER), JOKER);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuitPermutation([Suit; 5]);
+
+impl SuitPermutation {
+    /// Leaves every suit unchanged
+    pub const IDENTITY: SuitPermutation = SuitPermutation([Suit::None,
+        Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade]);
+
+    /// New permutation giving the new suits for clubs, diamonds, hearts,
+    /// and spades, in that order
+    pub fn new(to: [Suit; 4]) -> SuitPermutation {
+        debug_assert!(ALL_SUITS.iter().all(|s| to.contains(s)));
+        SuitPermutation([Suit::None, to[0], to[1], to[2], to[3]])
+    }
+
+    /// Iterate over all 24 permutations, starting with the identity
+    pub fn all() -> impl Iterator<Item = SuitPermutation> {
+        let mut idx = [0, 1, 2, 3];
+        let mut done = false;
+
+        std::iter::from_fn(move || {
+            if done { return None; }
+            let p = SuitPermutation::new(idx.map(|i| ALL_SUITS[i]));
+            done = ! next_permutation(&mut idx);
+            Some(p)
+        })
+    }
+
+    /// New suit for the given suit
+    pub fn apply_suit(&self, s: Suit) -> Suit {
+        self.0[s as usize]
+    }
+
+    /// Card with its suit renamed
+    pub fn apply(&self, c: Card) -> Card {
+        let s = c.suit();
+        if Suit::None == s { return c; }
+        Card::from_rank_suit(c.rank(), self.apply_suit(s))
+    }
+
+    /// Hand with the suits of all its cards renamed, in the same order
+    pub fn apply_hand(&self, h: &Hand) -> Hand {
+        let mut out = *h;
+        for i in 0..h.len() {
+            out[i] = self.apply(h[i]);
+        }
+        out
+    }
+
+    /// Permutation that undoes this one
+    pub fn inverse(&self) -> SuitPermutation {
+        let mut inv = SuitPermutation::IDENTITY;
+        for s in ALL_SUITS {
+            inv.0[self.apply_suit(s) as usize] = s;
+        }
+        inv
+    }
+}
+
+impl std::default::Default for SuitPermutation {
+    fn default() -> Self {
+        SuitPermutation::IDENTITY
+    }
+}
+
+// Lexicographic next permutation, false if already the last one
+fn next_permutation(a: &mut [usize]) -> bool {
+    let Some(i) = (1..a.len()).rev().find(|&i| a[i - 1] < a[i]) else {
+        return false;
+    };
+    let j = (i..a.len()).rev().find(|&j| a[j] > a[i - 1])
+        .expect("larger element after pivot");
+    a.swap(i - 1, j);
+    a[i..].reverse();
+    true
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/CanonicalHand) | Canonical member of a suit-isomorphism class
+///
+/// Since the permutation differs for each member of a class, `hands` alone
+/// is the key to use for caching results by class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalHand {
+    /// Canonical cards for each round, each sorted
+    pub hands: Vec<Hand>,
+    /// Number of distinct deals in the class
+    pub multiplicity: u32,
+    /// Permutation that takes the original cards to the canonical ones
+    pub permutation: SuitPermutation,
+}
+
+impl CanonicalHand {
+    /// All rounds together as a single hand
+    pub fn hand(&self) -> Hand {
+        let mut h = self.hands[0];
+        for r in self.hands[1..].iter() {
+            h.push_all(r.iter());
+        }
+        h
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojc_canonical_rounds) | Canonical form of cards dealt in rounds
+///
+/// Cards within each round are unordered, but rounds are kept apart, so
+/// hole cards "AhKh" with board "Qh" are a different class than "AhQh"
+/// with board "Kh". The canonical form is the greatest of the 24 suit
+/// renamings, comparing rounds in order (so the first round gets spades
+/// first, then hearts, and so on). The multiplicity is 24 divided by the
+/// number of renamings that leave it unchanged.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::cards::ojc_canonical_rounds;
+///
+/// let hole = Hand::new(DeckType::English).init(hand!("Td","9d"));
+/// let flop = Hand::new(DeckType::English).init(hand!("2c","7d","8h"));
+/// let c = ojc_canonical_rounds(&[hole, flop]);
+/// assert_eq!(c.hands[0].to_string(), "Ts9s");
+/// assert_eq!(c.hands[1].to_string(), "8h7s2d");
+/// assert_eq!(c.multiplicity, 24);
+/// assert_eq!(c.permutation.apply(NINE_OF_DIAMONDS), NINE_OF_SPADES);
+/// ```
+pub fn ojc_canonical_rounds(rounds: &[Hand]) -> CanonicalHand {
+    let mut best: Vec<Hand> = Vec::new();
+    let mut permutation = SuitPermutation::IDENTITY;
+    let mut ties: u32 = 0;
+
+    for p in SuitPermutation::all() {
+        let mapped: Vec<Hand> = rounds.iter()
+            .map(|h| p.apply_hand(h).sorted()).collect();
+
+        if best.is_empty() || mapped > best {
+            best = mapped;
+            permutation = p;
+            ties = 1;
+        } else if mapped == best {
+            ties += 1;
+        }
+    }
+    CanonicalHand { hands: best, multiplicity: 24 / ties, permutation }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojc_canonical) | Canonical form of a hand
+///
+/// Same as [ojc_canonical_rounds] with a single round.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::cards::ojc_canonical;
+///
+/// let h = Hand::new(DeckType::English).init(hand!("Kd","Ad"));
+/// let c = ojc_canonical(&h);
+/// assert_eq!(c.hand().to_string(), "AsKs");
+/// assert_eq!(c.multiplicity, 4);
+/// ```
+pub fn ojc_canonical(h: &Hand) -> CanonicalHand {
+    ojc_canonical_rounds(std::slice::from_ref(h))
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::error::Result;
+
+    #[test]
+    fn test_isomorphism() -> Result<()> {
+        let deck = Deck::new(DeckType::English);
+
+        assert_eq!(SuitPermutation::all().count(), 24);
+        for p in SuitPermutation::all() {
+            assert_eq!(p.inverse().inverse(), p);
+            for c in deck.iter() {
+                assert_eq!(p.inverse().apply(p.apply(c)), c);
+            }
+        }
+
+        // Known class counts, and multiplicities that cover every deal
+        for (k, classes, deals) in [(2, 169, 1326), (3, 1755, 22100)] {
+            let mut seen: HashMap<Vec<Hand>, u32> = HashMap::new();
+            for h in deck.combinations(k) {
+                let c = ojc_canonical(&h);
+                assert_eq!(c.permutation.apply_hand(&h).sorted(), c.hand());
+                *seen.entry(c.hands).or_insert(0) += 1;
+            }
+            assert_eq!(seen.len(), classes);
+            for (hands, count) in seen.iter() {
+                assert_eq!(ojc_canonical_rounds(hands).multiplicity, *count);
+            }
+            assert_eq!(seen.values().sum::<u32>(), deals);
+        }
+
+        let h = deck.new_hand().init(hand!("As","Ah"));
+        assert_eq!(ojc_canonical(&h).multiplicity, 6);
+        let h = deck.new_hand().init(hand!("Ac","Kh"));
+        assert_eq!(ojc_canonical(&h).multiplicity, 12);
+        let h = deck.new_hand().init(hand!("4c","2c","3c"));
+        let c = ojc_canonical(&h);
+        assert_eq!(c.hand().to_string(), "4s3s2s");
+        assert_eq!(c.multiplicity, 4);
+
+        // Rounds kept apart
+        let hole = deck.new_hand().init(hand!("Ah","Kh"));
+        let board = deck.new_hand().init(hand!("Qh"));
+        let c1 = ojc_canonical_rounds(&[hole, board]);
+        let hole = deck.new_hand().init(hand!("Ah","Qh"));
+        let board = deck.new_hand().init(hand!("Kh"));
+        let c2 = ojc_canonical_rounds(&[hole, board]);
+        assert_ne!(c1.hands, c2.hands);
+        assert_eq!(c1.hand().len(), 3);
+        assert_eq!(c1.multiplicity, 4);
+
+        let hole = deck.new_hand().init(hand!("Ah","Ac"));
+        let board = deck.new_hand().init(hand!("2h","3d","4s"));
+        let c = ojc_canonical_rounds(&[hole, board]);
+        assert_eq!(c.multiplicity, 24);
+        assert_eq!(c.hands[0].to_string(), "AsAh");
+        assert_eq!(c.hands[1].to_string(), "4d3c2s");
+
+        // Jokers stay put
+        let deck = Deck::new(DeckType::OneJoker);
+        let h = deck.new_hand().init(hand!("Jk","5d"));
+        let c = ojc_canonical(&h);
+        assert_eq!(c.hand().to_string(), "5sJk");
+        assert_eq!(c.multiplicity, 4);
+        Ok(())
+    }
+}
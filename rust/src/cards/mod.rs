@@ -25,3 +25,12 @@ pub use deck::*;
 pub mod hand;
 pub use hand::*;
 
+pub mod card_set;
+pub use card_set::*;
+
+
+pub mod isomorphism;
+pub use isomorphism::*;
+
+pub mod hand_indexer;
+pub use hand_indexer::*;
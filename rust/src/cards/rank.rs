@@ -22,7 +22,6 @@ use serde::{Serialize, Deserialize};
 /// See [Ace](https://github.com/lcrocker/ojpoker/wiki/Ace) and
 /// [Knight](https://github.com/lcrocker/ojpoker/wiki/Knight) @ wiki
 /// for details.
-
 #[repr(u8)]
 #[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug, Hash, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
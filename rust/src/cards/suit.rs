@@ -16,7 +16,6 @@ use serde::{Serialize, Deserialize};
 ///
 /// Specific numbers do matter: I do a lot of math with them to optimize
 /// things, and the same numbers are used in the other languages.
-
 #[repr(u8)]
 #[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug, Hash, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
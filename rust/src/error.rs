@@ -39,6 +39,10 @@ pub enum Error {
     BadHand(String),
     /// Badly formed description
     BadDescription(String),
+    /// Game can't be played as set up
+    BadGame(String),
+    /// Action not allowed by the rules at this point
+    IllegalAction(String),
 }
 
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/Result) | Library-related result type
@@ -81,6 +85,10 @@ impl std::fmt::Display for Error {
                 => write!(f, "bad hand: {}", s),
             Error::BadDescription(s)
                 => write!(f, "bad description: {}", s),
+            Error::BadGame(s)
+                => write!(f, "bad game: {}", s),
+            Error::IllegalAction(s)
+                => write!(f, "illegal action: {}", s),
         }
     }
 }
@@ -0,0 +1,219 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Batch_Evaluation) | Evaluating many hands at once
+//!
+//! Simulations evaluate millions of hands with the same scale, so the
+//! batch functions look up the evaluator once, load any lookup tables up
+//! front (returning an error instead of panicking if they're missing), and
+//! can split the work over several threads. Each value is exactly what
+//! [Scale::value] gives for the same hand.
+
+use crate::error::{Error, Result};
+use crate::cards::*;
+use crate::poker::*;
+use crate::cards::hand::MAX_HAND_SIZE;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/BatchEvaluator) | Evaluate slices of hands
+///
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::BatchEvaluator;
+///
+/// let mut deck = Deck::new(DeckType::English);
+/// let hands: Vec<Hand> = (0..1000).map(|_| {
+///     deck.refill_and_shuffle();
+///     deck.new_hand().init(deck.draw(7))
+/// }).collect();
+///
+/// let mut values = vec![0; hands.len()];
+/// BatchEvaluator::new(Scale::HighHand).threads(4)
+///     .values(&hands, &mut values).unwrap();
+/// assert_eq!(values[7], Scale::HighHand.value(&hands[7]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchEvaluator {
+    /// Scale used to evaluate every hand
+    pub scale: Scale,
+    /// Number of threads to use, or 0 for one per available CPU
+    pub threads: usize,
+}
+
+impl BatchEvaluator {
+    /// New single-threaded evaluator for the given scale
+    pub fn new(scale: Scale) -> Self {
+        BatchEvaluator { scale, threads: 1 }
+    }
+
+    /// Set number of threads, 0 meaning one per available CPU
+    pub fn threads(mut self, n: usize) -> Self {
+        self.threads = n;
+        self
+    }
+
+    /// Evaluate each hand into the corresponding element of `out`
+    pub fn values(&self, hands: &[Hand], out: &mut [HandValue]) -> Result<()> {
+        if hands.len() != out.len() {
+            return Err(Error::BadHand(format!("{} hands for {} values",
+                hands.len(), out.len())));
+        }
+        let eval = self.prepare()?;
+
+        self.run(hands, 1, out, |hs, vs| {
+            for (h, v) in hs.iter().zip(vs.iter_mut()) {
+                *v = eval(h);
+            }
+        });
+        Ok(())
+    }
+
+    /// Evaluate hands of `stride` cards each, laid end to end in `cards`
+    ///
+    /// Each hand is built as `Hand::new(scale.deck_type()).init(...)`
+    /// would, so aces are fixed for the scale's deck.
+    pub fn values_of_cards(&self, cards: &[Card], stride: usize,
+    out: &mut [HandValue]) -> Result<()> {
+        if 0 == stride || stride > MAX_HAND_SIZE {
+            return Err(Error::BadHand(format!("{} cards per hand", stride)));
+        }
+        if cards.len() != stride * out.len() {
+            return Err(Error::BadHand(format!("{} cards for {} hands of {}",
+                cards.len(), out.len(), stride)));
+        }
+        let eval = self.prepare()?;
+        let deck = self.scale.deck_type();
+
+        self.run(cards, stride, out, |cs, vs| {
+            let mut h = Hand::new(deck);
+
+            for (c, v) in cs.chunks_exact(stride).zip(vs.iter_mut()) {
+                h.set(c.iter().copied());
+                *v = eval(&h);
+            }
+        });
+        Ok(())
+    }
+
+    // Find the evaluator and make sure its tables are loaded
+    fn prepare(&self) -> Result<HandEvaluator> {
+        if Scale::None == self.scale {
+            return Err(Error::BadHand("no scale for batch".into()));
+        }
+        #[cfg(all(feature = "flate2", feature = "sha2"))]
+        for t in crate::poker::tables::ojp_scale_tables(self.scale) {
+            t.try_load()?;
+        }
+        Ok(self.scale.evaluator())
+    }
+
+    // Split input (`per` items for each output) and output into one chunk
+    // per thread
+    fn run<T, F>(&self, input: &[T], per: usize, out: &mut [HandValue], f: F)
+    where T: Sync, F: Fn(&[T], &mut [HandValue]) + Sync {
+        let threads = match self.threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        }.min(out.len() / MIN_HANDS_PER_THREAD).max(1);
+
+        if 1 == threads {
+            f(input, out);
+            return;
+        }
+        let chunk = out.len().div_ceil(threads);
+
+        std::thread::scope(|s| {
+            for (i, o) in input.chunks(chunk * per).zip(out.chunks_mut(chunk)) {
+                let f = &f;
+                s.spawn(move || f(i, o));
+            }
+        });
+    }
+}
+
+// Not worth starting a thread for fewer
+const MIN_HANDS_PER_THREAD: usize = 1024;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_value_batch) | Evaluate slice of hands
+///
+/// Single-threaded shortcut for [BatchEvaluator::values].
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::ojp_value_batch;
+///
+/// let hands = [
+///     Hand::new(DeckType::English).init(hand!("9s","As","9d","Ks","Ah")),
+///     Hand::new(DeckType::English).init(hand!("Qs","Js","Ts","9s","8s")),
+/// ];
+/// let mut values = [0; 2];
+/// ojp_value_batch(&hands, Scale::HighHand, &mut values).unwrap();
+/// assert!(values[1] < values[0]);
+/// ```
+pub fn ojp_value_batch(hands: &[Hand], g: Scale, out: &mut [HandValue])
+-> Result<()> {
+    BatchEvaluator::new(g).values(hands, out)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_value_batch_cards) | Evaluate hands laid end to end in slice of cards
+///
+/// Single-threaded shortcut for [BatchEvaluator::values_of_cards].
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::ojp_value_batch_cards;
+///
+/// let cards = hand!("Ah","Kh","Qh","Jh","Th", "7s","5d","4c","3h","2s");
+/// let mut values = [0; 2];
+/// ojp_value_batch_cards(&cards, 5, Scale::DeuceToSeven, &mut values).unwrap();
+/// assert!(values[1] < values[0]);
+/// ```
+pub fn ojp_value_batch_cards(cards: &[Card], stride: usize, g: Scale,
+out: &mut [HandValue]) -> Result<()> {
+    BatchEvaluator::new(g).values_of_cards(cards, stride, out)
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_evaluation() -> Result<()> {
+        for g in [Scale::HighHand, Scale::AceToFive, Scale::DeuceToSeven,
+            Scale::AceToSix, Scale::Badugi, Scale::PaiGow, Scale::Mexican,
+            Scale::ThreeCard] {
+
+            let mut deck = g.new_deck();
+            let n = g.complete_hand() + 2;
+            let mut cards: Vec<Card> = Vec::new();
+            let hands: Vec<Hand> = (0..3000).map(|_| {
+                deck.refill_and_shuffle();
+                let h = deck.new_hand().init(deck.draw(n));
+                cards.extend(h.iter());
+                h
+            }).collect();
+            let expected: Vec<HandValue> = hands.iter()
+                .map(|h| g.value(h)).collect();
+
+            for threads in [1, 3, 0] {
+                let b = BatchEvaluator::new(g).threads(threads);
+                let mut out = vec![0; hands.len()];
+                b.values(&hands, &mut out)?;
+                assert_eq!(out, expected);
+
+                let mut out = vec![0; hands.len()];
+                b.values_of_cards(&cards, n, &mut out)?;
+                assert_eq!(out, expected);
+            }
+        }
+        let mut out = [0; 2];
+        let hands = [Hand::default(); 3];
+        assert!(ojp_value_batch(&hands, Scale::HighHand, &mut out).is_err());
+        assert!(ojp_value_batch(&hands[..2], Scale::None, &mut out).is_err());
+
+        let cards = hand!("Ah","Kh","Qh","Jh","Th","9h");
+        assert!(ojp_value_batch_cards(&cards, 5, Scale::HighHand, &mut out).is_err());
+        assert!(ojp_value_batch_cards(&cards, 0, Scale::HighHand, &mut []).is_err());
+        ojp_value_batch_cards(&cards, 3, Scale::ThreeCard, &mut out)?;
+        assert!(out[0] < out[1]);
+        Ok(())
+    }
+}
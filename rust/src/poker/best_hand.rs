@@ -0,0 +1,238 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Best_Hand) | Best hand from hole cards and board
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::error::{Error, Result};
+use crate::cards::*;
+use crate::poker::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/HoleUsage) | How many hole cards must play
+///
+/// Rule for how a player's hole cards combine with the board to make a
+/// final hand.
+/// ```rust
+/// use onejoker::poker::HoleUsage;
+///
+/// assert_eq!(HoleUsage::OMAHA, HoleUsage::Exactly(2));
+/// assert!(HoleUsage::AtMost(1).allows(0));
+/// assert!(! HoleUsage::AtLeast(1).allows(0));
+/// assert_eq!("exactly 2".parse::<HoleUsage>().unwrap(), HoleUsage::OMAHA);
+/// assert_eq!(HoleUsage::AtMost(1).to_string(), "at most 1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HoleUsage {
+    /// Any combination of hole and board cards (hold'em, pineapple)
+    #[default]
+    Any,
+    /// Exactly this many hole cards (omaha, courchevel)
+    Exactly(usize),
+    /// At least this many hole cards
+    AtLeast(usize),
+    /// At most this many hole cards
+    AtMost(usize),
+}
+
+impl HoleUsage {
+    /// Texas hold'em: any five of seven cards
+    pub const HOLDEM: HoleUsage = HoleUsage::Any;
+    /// Pineapple and crazy pineapple: any cards of those kept
+    pub const PINEAPPLE: HoleUsage = HoleUsage::Any;
+    /// Omaha with 4, 5, or 6 hole cards: exactly two
+    pub const OMAHA: HoleUsage = HoleUsage::Exactly(2);
+    /// Courchevel: five hole cards, exactly two play
+    pub const COURCHEVEL: HoleUsage = HoleUsage::Exactly(2);
+
+    /// Does the rule allow using exactly `n` hole cards?
+    pub const fn allows(&self, n: usize) -> bool {
+        match *self {
+            HoleUsage::Any => true,
+            HoleUsage::Exactly(m) => n == m,
+            HoleUsage::AtLeast(m) => n >= m,
+            HoleUsage::AtMost(m) => n <= m,
+        }
+    }
+}
+
+impl std::fmt::Display for HoleUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            HoleUsage::Any => write!(f, "any"),
+            HoleUsage::Exactly(n) => write!(f, "exactly {}", n),
+            HoleUsage::AtLeast(n) => write!(f, "at least {}", n),
+            HoleUsage::AtMost(n) => write!(f, "at most {}", n),
+        }
+    }
+}
+
+impl std::str::FromStr for HoleUsage {
+    type Err = Error;
+
+    /// Parse "any", "exactly 2", "at least 1", or "at most 1"
+    fn from_str(text: &str) -> Result<HoleUsage> {
+        let words: Vec<String> = text.split_whitespace()
+            .map(str::to_lowercase).collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let count = |s: &str| s.parse::<usize>().map_err(|_|
+            Error::ParseOther(format!("bad hole card count: {}", s)));
+
+        match words[..] {
+            ["any"] => Ok(HoleUsage::Any),
+            ["exactly", n] => Ok(HoleUsage::Exactly(count(n)?)),
+            ["at", "least", n] => Ok(HoleUsage::AtLeast(count(n)?)),
+            ["at", "most", n] => Ok(HoleUsage::AtMost(count(n)?)),
+            _ => Err(Error::ParseOther(format!("bad hole card usage: {}", text))),
+        }
+    }
+}
+
+// Hole card counts that are both allowed and possible, along with the
+// final hand size
+pub(crate) fn hole_counts(hole: usize, board: usize, g: Scale, rule: HoleUsage)
+-> Result<(usize, Vec<usize>)> {
+    let size = g.complete_hand().min(hole + board);
+    let counts: Vec<usize> = (0..=size.min(hole))
+        .filter(|&n| size - n <= board && rule.allows(n))
+        .collect();
+
+    if counts.is_empty() {
+        return Err(Error::BadHand(format!("no {}-card hand from {} hole \
+            and {} board cards using {:?}", size, hole, board, rule)));
+    }
+    Ok((size, counts))
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_best_hand) | Best hand from hole cards and board
+///
+/// Find the best hand for the given scale that can be made from the hole
+/// cards and board under the usage rule, returning its value and cards.
+/// If there are too few cards for a complete hand, all of them play.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_best_hand, HoleUsage};
+///
+/// let hole = Hand::new(DeckType::English).init(hand!("Ah","Kh","Qh","Jh"));
+/// let board = Hand::new(DeckType::English).init(hand!("Th","9c","8h","3h","2s"));
+/// let (v, h) = ojp_best_hand(&hole, &board, Scale::HighHand, HoleUsage::OMAHA).unwrap();
+/// let d = Scale::HighHand.description(&h, v);
+/// assert_eq!(d.full_text(), "flush: ace, king, ten, eight, trey");
+/// ```
+pub fn ojp_best_hand(hole: &Hand, board: &Hand, g: Scale, rule: HoleUsage)
+-> Result<(HandValue, Hand)> {
+    let hole = hole.convert_decktype(g.deck_type());
+    let board = board.convert_decktype(g.deck_type());
+    let (size, counts) = hole_counts(hole.len(), board.len(), g, rule)?;
+    let mut best = HAND_VALUE_WORST;
+    let mut best_hand = hole;
+
+    for n in counts {
+        for hc in hole.combinations(n) {
+            for bc in board.combinations(size - n) {
+                let mut h = hc;
+                h.push_all(bc.iter());
+                let v = g.value(&h);
+                if v < best {
+                    best = v;
+                    best_hand = h;
+                }
+            }
+        }
+    }
+    Ok((best, best_hand))
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_best_value) | Best hand value from hole cards and board
+///
+/// Same as [ojp_best_hand] but only the value is returned, which lets the
+/// "any" rule go straight to the scale's evaluator for the pooled cards.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_best_value, HoleUsage};
+///
+/// let hole = Hand::new(DeckType::English).init(hand!("As","Ad","Kc","Kd"));
+/// let board = Hand::new(DeckType::English).init(hand!("Ah","Ac","7d","4s","2c"));
+/// let holdem = ojp_best_value(&hole, &board, Scale::HighHand, HoleUsage::Any).unwrap();
+/// let omaha = ojp_best_value(&hole, &board, Scale::HighHand, HoleUsage::OMAHA).unwrap();
+/// assert!(holdem < omaha);    // four aces vs. aces full
+/// ```
+pub fn ojp_best_value(hole: &Hand, board: &Hand, g: Scale, rule: HoleUsage)
+-> Result<HandValue> {
+    if HoleUsage::Any == rule {
+        let mut h = hole.convert_decktype(g.deck_type());
+        h.push_all(board.iter());
+        return Ok(g.value(&h));
+    }
+    Ok(ojp_best_hand(hole, board, g, rule)?.0)
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_hand() -> Result<()> {
+        let deck = Deck::new(DeckType::English);
+        let board = deck.new_hand().init(hand!("Th","9h","8d","3h","Qh"));
+        let g = Scale::HighHand;
+
+        // Omaha 4, 5, and 6 card: a single heart doesn't make a flush
+        for text in ["AhKc7c2d", "AhKc7c2d5s", "AhKc7c2d5s4c"] {
+            let hole = deck.new_hand().init(card_parse(text));
+            let (any, ah) = ojp_best_hand(&hole, &board, g, HoleUsage::Any)?;
+            assert_eq!(any, ojp_best_value(&hole, &board, g, HoleUsage::Any)?);
+            let (v, h) = ojp_best_hand(&hole, &board, g, HoleUsage::OMAHA)?;
+            assert_eq!(g.description(&ah, any).level, HandLevel::Flush);
+            assert_eq!(g.description(&h, v).level, HandLevel::NoPair);
+            assert!(any < v);
+        }
+
+        // Two hole cards with the J-T-9-8 straight
+        let hole = deck.new_hand().init(hand!("Jc","7c","Qd","2d"));
+        let (v, h) = ojp_best_hand(&hole, &board, g, HoleUsage::OMAHA)?;
+        let d = g.description(&h, v);
+        assert_eq!(d.level, HandLevel::Straight);
+        assert_eq!(d.hand[0].rank(), Rank::Queen);
+        assert_eq!(h.len(), 5);
+
+        // Courchevel: five hole cards, exactly two play
+        let hole = deck.new_hand().init(hand!("Kh","2h","Jc","Js","Jd"));
+        let (v, h) = ojp_best_hand(&hole, &board, g, HoleUsage::COURCHEVEL)?;
+        assert_eq!(g.description(&h, v).level, HandLevel::Flush);
+        assert_eq!(h.iter().filter(|&c| hole.contains(c)).count(), 2);
+
+        // Pineapple: after the discard, like hold'em
+        let hole = deck.new_hand().init(hand!("Qc","Qd"));
+        let v = ojp_best_value(&hole, &board, g, HoleUsage::PINEAPPLE)?;
+        let (v2, _) = ojp_best_hand(&hole, &board, g, HoleUsage::PINEAPPLE)?;
+        assert_eq!(v, v2);
+
+        // Playing the board vs. at least one hole card
+        let board = deck.new_hand().init(hand!("Ah","Kh","Qh","Jh","Th"));
+        let hole = deck.new_hand().init(hand!("2c","3d"));
+        let (v, _) = ojp_best_hand(&hole, &board, g, HoleUsage::AtMost(0))?;
+        let (v2, _) = ojp_best_hand(&hole, &board, g, HoleUsage::AtLeast(1))?;
+        assert_eq!(v, ojp_best_value(&hole, &board, g, HoleUsage::Any)?);
+        assert!(v < v2);
+
+        // Other scales, and partial hands
+        let hole = deck.new_hand().init(hand!("As","2c","Kd","Kc"));
+        let board = deck.new_hand().init(hand!("3d","4h","Qs","5c","9h"));
+        let (v, h) = ojp_best_hand(&hole, &board, Scale::AceToFive, HoleUsage::OMAHA)?;
+        assert_eq!(Scale::AceToFive.description(&h, v).hand[0].rank(), Rank::Five);
+        let flop = deck.new_hand().init(hand!("3d","4h","Qs"));
+        let (_, h) = ojp_best_hand(&hole, &flop, Scale::Badugi, HoleUsage::OMAHA)?;
+        assert_eq!(h.len(), 4);
+
+        let empty = deck.new_hand();
+        let (_, h) = ojp_best_hand(&hole, &empty, g, HoleUsage::Any)?;
+        assert_eq!(h.len(), 4);
+        assert!(ojp_best_hand(&hole, &empty, g, HoleUsage::OMAHA).is_err());
+        assert!(ojp_best_hand(&hole, &flop, g, HoleUsage::Exactly(5)).is_err());
+        Ok(())
+    }
+}
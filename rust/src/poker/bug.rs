@@ -1,6 +1,8 @@
 //! [wiki](https://github.com/lcrocker/ojpoker/wiki/Bug) | Bug scan and replacement
 
 use crate::prelude::*;
+use crate::poker::{HandEvaluator, HAND_VALUE_WORST};
+use crate::poker::reference_evaluators::MEXICAN_RANK_ORDER;
 
 /// Return the result of scanning the hand for information necessary
 /// for replacing the bug, if present.
@@ -40,11 +42,45 @@ fn first_ace(mask: u8, index: i8) -> Option<BugScanResult> {
     })
 }
 
+/// Scan hand of any size for one bug that plays as the lowest rank not
+/// already present, as in ace-to-five lowball
+pub fn ojp_bug_scan_low_1(h: &Hand) -> Option<BugScanResult> {
+    let mut rank_mask: u16 = 0;
+    let mut joker_index: i8 = -1;
+
+    for i in 0..h.len() {
+        if h[i].is_joker() {
+            joker_index = i as i8;
+        } else {
+            rank_mask |= 1 << h[i].rank() as u8;
+        }
+    }
+    if -1 == joker_index {
+        return None;
+    }
+    // Skip knights unless the deck has them
+    if ! h.deck_type().has(Card::from_rank_suit(Rank::Knight, Suit::Spade)) {
+        rank_mask |= 1 << Rank::Knight as u8;
+    }
+    let mut needed_rank = Rank::LowAce;
+    while 0 != rank_mask & (1 << needed_rank as u8) {
+        needed_rank = Rank::from_u8(needed_rank as u8 + 1);
+    }
+    Some(BugScanResult {
+        index: joker_index as u8,
+        replacement: Card::from_rank_suit(needed_rank, Suit::Spade),
+    })
+}
+
 /// Scan incomplete hand for a bug
 pub fn ojp_bug_scan_p_1(h: &Hand, g: Scale) -> Option<BugScanResult> {
     debug_assert!(h.len() < 5);
     debug_assert!(g.deck_type().has(JOKER));
 
+    if Scale::AceToFiveBug == g {
+        return ojp_bug_scan_low_1(h);
+    }
+
     let mut ace_mask: u8 = 0;
     let mut joker_index: i8 = -1;
 
@@ -97,28 +133,13 @@ pub fn ojp_bug_scan_5_1(h: &Hand, g: Scale) -> Option<BugScanResult> {
     }
     // Find lowest rank not already present in hand
     if Scale::AceToFiveBug == g {
-        rank_mask >>= 1;
-        needed_rank = Rank::LowAce;
-
-        let mut loop_guard = 20;
-        loop {
-            loop_guard -= 1;
-            assert!(loop_guard > 0);
-
-            if 0 == (rank_mask & 1) {
-                break;
-            }
-            needed_rank = Rank::from_u8(needed_rank as u8 + 1);
-            rank_mask >>= 1;
-        }
-        return Some(BugScanResult {
-            index: joker_index as u8,
-            replacement: Card::from_rank_suit(needed_rank, Suit::Spade),
-        });
+        return ojp_bug_scan_low_1(h);
     }
     // Handle the 2-3-4-5-Jk case: replace with A in pai gow, 6 in other games
     if Scale::PaiGow == g && 0b111101 == rank_mask {
         needed_rank = Rank::Ace;
+    } else if g.spanish_gap() {
+        needed_rank = spanish_rank_needed(rank_mask);
     } else {
         needed_rank = straight_rank_needed_bsearch(rank_mask);
     }
@@ -129,29 +150,21 @@ pub fn ojp_bug_scan_5_1(h: &Hand, g: Scale) -> Option<BugScanResult> {
             replacement: Card::from_rank_suit(needed_rank, needed_suit),
         });
     }
-    // Flush: find highest rank not already present in hand
+    // Flush: find highest rank not already present in hand, skipping
+    // any ranks (like knights or the spanish gap) not in the deck
     if needed_suit != Suit::None {
-        if g.low_aces() {
-            rank_mask <<= 1;
-            needed_rank = Rank::King;
-        } else {
-            needed_rank = Rank::Ace;
-        }
-        let mut loop_guard = 20;
-        loop {
-            loop_guard -= 1;
-            assert!(loop_guard > 0);
+        let top = if g.low_aces() { Rank::King } else { Rank::Ace };
 
-            if 0 == (rank_mask & 0b1000_0000_0000_0000) {
-                break;
+        for r in (1..=(top as u8)).rev() {
+            let c = Card::from_rank_suit(Rank::from_u8(r), needed_suit);
+
+            if 0 == (rank_mask & (1 << r)) && g.deck_type().has(c) {
+                return Some(BugScanResult {
+                    index: joker_index as u8,
+                    replacement: c,
+                });
             }
-            needed_rank = Rank::from_u8(needed_rank as u8 - 1);
-            rank_mask <<= 1;
         }
-        return Some(BugScanResult {
-            index: joker_index as u8,
-            replacement: Card::from_rank_suit(needed_rank, needed_suit),
-        });
     }
     // Fill straight
     if needed_rank != Rank::None {
@@ -163,6 +176,48 @@ pub fn ojp_bug_scan_5_1(h: &Hand, g: Scale) -> Option<BugScanResult> {
     first_ace(ace_mask, joker_index)
 }
 
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_bug_replace) | Replace bug in hand of any size
+///
+/// Return a copy of the hand with the bug replaced by the card it stands
+/// for, along with that card, or `None` if there is no bug. For hands larger
+/// than a complete hand, the best complete hand according to the given
+/// evaluator is moved to the front, with the remaining cards following.
+pub fn ojp_bug_replace(h: &Hand, g: Scale, eval: HandEvaluator)
+-> (Hand, Option<Card>) {
+    let k = g.complete_hand();
+
+    if h.len() <= k {
+        let sr = if h.len() < k {
+            ojp_bug_scan_p_1(h, g)
+        } else {
+            ojp_bug_scan_5_1(h, g)
+        };
+        let Some(sr) = sr else {
+            return (*h, None);
+        };
+        let mut bh = *h;
+        bh[sr.index as usize] = sr.replacement;
+        return (bh, Some(sr.replacement));
+    }
+    let mut best = HAND_VALUE_WORST;
+    let mut best_sub = *h;
+
+    for sub in h.combinations(k) {
+        let v = eval(&sub);
+        if v < best {
+            best = v;
+            best_sub = sub;
+        }
+    }
+    let (mut bh, replacement) = ojp_bug_replace(&best_sub, g, eval);
+    for i in 0..h.len() {
+        if ! best_sub.contains(h[i]) {
+            bh.push(h[i]);
+        }
+    }
+    (bh, replacement)
+}
+
 // For five-card hands, the rank mask must exactly equal one of the
 // target bit patterns, so we can use a binary search.
 fn straight_rank_needed_bsearch(rank_mask: u16) -> Rank {
@@ -191,6 +246,41 @@ fn straight_rank_needed_bsearch(rank_mask: u16) -> Rank {
     Rank::None
 }
 
+// Ranks of the Mexican deck by position in the straight sequence
+const SPANISH_STRAIGHT_RANKS: [Rank; 12] = [
+    Rank::None, Rank::None, Rank::Deuce, Rank::Trey, Rank::Four, Rank::Five,
+    Rank::Six, Rank::Seven, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+];
+
+// Find the highest straight the bug can complete with the 8s, 9s, and
+// 10s missing, so that 7 and J are consecutive.
+fn spanish_rank_needed(rank_mask: u16) -> Rank {
+    let mut ord_mask: u16 = 0;
+
+    for (r, &o) in MEXICAN_RANK_ORDER.iter().enumerate().skip(1) {
+        if 0 != rank_mask & (1 << r) {
+            if o < 2 {
+                return Rank::None;
+            }
+            ord_mask |= 1 << o;
+        }
+    }
+    if 4 != ord_mask.count_ones() {
+        return Rank::None;
+    }
+    for top in (6..12).rev() {
+        let window: u16 = 0b11111 << (top - 4);
+        if 0 == ord_mask & !window {
+            return SPANISH_STRAIGHT_RANKS[(window & !ord_mask).trailing_zeros() as usize];
+        }
+    }
+    let wheel: u16 = 0b1000_0011_1100;
+    if 0 == ord_mask & !wheel {
+        return SPANISH_STRAIGHT_RANKS[(wheel & !ord_mask).trailing_zeros() as usize];
+    }
+    Rank::None
+}
+
 const HIGH_BUG_STRAIGHT_PATTERNS: [(u16, Rank); 41] = [
     (0b0000000000111101, Rank::Six),
     (0b0000000001011101, Rank::Five),
@@ -0,0 +1,1253 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Engine) | Dealing and betting a whole hand
+//!
+//! [Engine] plays one hand of any game in [GameRules] from a [Deck]. It
+//! posts antes and blinds or the bring-in, deals each street, runs the
+//! draws, asks each player in turn to act, and enforces the betting
+//! structure. When the betting is done it shows down with the game's
+//! scales (via [ojp_best_value] and [ojp_split_description]), splits the
+//! main and side pots, and leaves a [HandRecord] from which the whole hand
+//! can be replayed.
+
+use std::collections::VecDeque;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::error::{Error, Result};
+use crate::cards::*;
+use crate::poker::*;
+
+/// Amount of chips
+pub type Chips = u64;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Limit) | Betting structure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Limit {
+    /// Bet or raise up to a whole stack
+    #[default]
+    NoLimit,
+    /// Raise up to the size of the pot after calling
+    PotLimit,
+    /// Every bet and raise is the fixed size for the street
+    FixedLimit,
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Stakes) | Forced bets and bet sizes
+///
+/// Games dealing an up card on the first street (stud games) use antes
+/// and the bring-in and ignore the blinds; other games use antes and
+/// blinds and ignore the bring-in. In fixed-limit games every bet and
+/// raise is `small_bet` for the first half of the streets and `big_bet`
+/// after that, with at most `cap` bets and raises per round (0 for no cap)
+/// unless only two players are left. In no-limit and pot-limit games those
+/// are the smallest bets allowed.
+/// ```rust
+/// use onejoker::poker::{Stakes, Limit};
+///
+/// let nl = Stakes::no_limit(1, 2);
+/// let stud = Stakes::fixed_limit(10, 20).ante(1).bring_in(3);
+/// assert_eq!(stud.limit, Limit::FixedLimit);
+/// assert_eq!(stud.bet_size(4, 5), 20);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stakes {
+    /// Betting structure
+    pub limit: Limit,
+    /// Posted by every player before the deal
+    pub ante: Chips,
+    /// Posted by the first player after the button (the button itself
+    /// when heads-up)
+    pub small_blind: Chips,
+    /// Posted by the player after the small blind
+    pub big_blind: Chips,
+    /// Forced opening bet of the lowest up card (highest in low games)
+    pub bring_in: Chips,
+    /// Bet size for early streets
+    pub small_bet: Chips,
+    /// Bet size for later streets
+    pub big_bet: Chips,
+    /// Most bets and raises per fixed-limit round, or 0 for no cap
+    pub cap: usize,
+}
+
+impl Stakes {
+    /// No-limit with the given blinds
+    pub const fn no_limit(small_blind: Chips, big_blind: Chips) -> Self {
+        Stakes {
+            limit: Limit::NoLimit, ante: 0, small_blind, big_blind,
+            bring_in: 0, small_bet: big_blind, big_bet: big_blind, cap: 0,
+        }
+    }
+
+    /// Pot-limit with the given blinds
+    pub const fn pot_limit(small_blind: Chips, big_blind: Chips) -> Self {
+        let mut s = Stakes::no_limit(small_blind, big_blind);
+        s.limit = Limit::PotLimit;
+        s
+    }
+
+    /// Fixed-limit with the given bet sizes, blinds of half and one small
+    /// bet, and a cap of four bets
+    pub const fn fixed_limit(small_bet: Chips, big_bet: Chips) -> Self {
+        Stakes {
+            limit: Limit::FixedLimit, ante: 0, small_blind: small_bet / 2,
+            big_blind: small_bet, bring_in: 0, small_bet, big_bet, cap: 4,
+        }
+    }
+
+    /// Set ante
+    pub const fn ante(mut self, n: Chips) -> Self {
+        self.ante = n;
+        self
+    }
+
+    /// Set blinds
+    pub const fn blinds(mut self, small_blind: Chips, big_blind: Chips) -> Self {
+        self.small_blind = small_blind;
+        self.big_blind = big_blind;
+        self
+    }
+
+    /// Set bring-in
+    pub const fn bring_in(mut self, n: Chips) -> Self {
+        self.bring_in = n;
+        self
+    }
+
+    /// Set fixed-limit cap on bets and raises, 0 for none
+    pub const fn cap(mut self, n: usize) -> Self {
+        self.cap = n;
+        self
+    }
+
+    /// Bet size for the given street of a game with `streets` streets
+    pub const fn bet_size(&self, street: usize, streets: usize) -> Chips {
+        if street >= streets / 2 { self.big_bet } else { self.small_bet }
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Action) | Betting action
+///
+/// Bets and raises give the player's total bet for the street, not the
+/// amount added. A bet or raise smaller than the minimum is allowed only
+/// when it puts the player all in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Action {
+    /// Give up the hand
+    Fold,
+    /// Pass with nothing to call
+    Check,
+    /// Match the current bet, or as much of it as the stack covers
+    Call,
+    /// Post the bring-in instead of completing
+    BringIn,
+    /// Open the betting to the given total
+    Bet(Chips),
+    /// Raise the betting to the given total
+    Raise(Chips),
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/LegalActions) | Actions open to the player to act
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LegalActions {
+    /// Seat of the player to act
+    pub seat: usize,
+    /// May fold (not when bringing in)
+    pub fold: bool,
+    /// May check
+    pub check: bool,
+    /// Chips needed to call, if there is a bet to call
+    pub call: Option<Chips>,
+    /// Chips needed to bring in, if bringing in
+    pub bring_in: Option<Chips>,
+    /// Smallest and largest totals to bet, if opening is allowed
+    pub bet: Option<(Chips, Chips)>,
+    /// Smallest and largest totals to raise to, if raising is allowed
+    pub raise: Option<(Chips, Chips)>,
+}
+
+impl LegalActions {
+    /// Is the action allowed?
+    pub fn allows(&self, a: Action) -> bool {
+        let within = |r: Option<(Chips, Chips)>, n: Chips|
+            r.is_some_and(|(lo, hi)| lo <= n && n <= hi);
+
+        match a {
+            Action::Fold => self.fold,
+            Action::Check => self.check,
+            Action::Call => self.call.is_some(),
+            Action::BringIn => self.bring_in.is_some(),
+            Action::Bet(n) => within(self.bet, n),
+            Action::Raise(n) => within(self.raise, n),
+        }
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Phase) | What the engine is waiting for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Betting action from the given seat
+    Betting(usize),
+    /// Discards from the given seat
+    Drawing(usize),
+    /// Hand is over
+    Complete,
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Event) | One step of a hand
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Event {
+    /// Ante posted
+    Ante {
+        /// Seat posting
+        seat: usize,
+        /// Chips posted
+        amount: Chips,
+    },
+    /// Blind posted
+    Blind {
+        /// Seat posting
+        seat: usize,
+        /// Chips posted
+        amount: Chips,
+    },
+    /// Start of the given street, counting from 0
+    Street(usize),
+    /// Cards dealt to a player
+    Deal {
+        /// Seat dealt to
+        seat: usize,
+        /// Cards dealt face down
+        down: Vec<Card>,
+        /// Cards dealt face up
+        up: Vec<Card>,
+    },
+    /// Community cards dealt
+    Board(Vec<Card>),
+    /// Betting action
+    Act {
+        /// Seat acting
+        seat: usize,
+        /// Action taken
+        action: Action,
+        /// Chips put in the pot
+        amount: Chips,
+    },
+    /// Cards replaced in a draw
+    Draw {
+        /// Seat drawing
+        seat: usize,
+        /// Cards thrown away
+        discards: Vec<Card>,
+        /// Cards dealt in their place
+        cards: Vec<Card>,
+    },
+    /// Discards shuffled into the new bottom of the deck, in this order
+    Reshuffle(Vec<Card>),
+    /// Hand shown down
+    Show {
+        /// Seat showing
+        seat: usize,
+        /// Value of the (high) hand
+        high: HandValue,
+        /// Value of the qualifying low hand of a split game
+        low: Option<HandValue>,
+    },
+    /// Uncalled bet returned
+    Refund {
+        /// Seat getting chips back
+        seat: usize,
+        /// Chips returned
+        amount: Chips,
+    },
+    /// Pot or part of a pot won
+    Win {
+        /// Winning seat
+        seat: usize,
+        /// Chips won
+        amount: Chips,
+    },
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/HandRecord) | Complete record of a hand
+///
+/// Everything needed to replay the hand: the rules of the game, stakes,
+/// starting stacks, button, and deck, followed by every event in order.
+/// The rules are recorded in full, so hands of games built in code or
+/// loaded from a file replay without them being registered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HandRecord {
+    /// Rules of the game
+    pub rules: GameRules,
+    /// Forced bets and bet sizes
+    pub stakes: Stakes,
+    /// Stack of each seat before the hand
+    pub stacks: Vec<Chips>,
+    /// Seat of the dealer button
+    pub button: usize,
+    /// Deck before the deal, top card last
+    pub deck: Vec<Card>,
+    /// Everything that happened
+    pub events: Vec<Event>,
+}
+
+impl HandRecord {
+    /// Replay the hand under the recorded rules
+    pub fn replay(&self) -> Result<Engine> {
+        Engine::replay(&self.rules, self)
+    }
+
+    /// Chips won (or lost, if negative) by each seat
+    pub fn net(&self) -> Vec<i64> {
+        let mut net = vec![0; self.stacks.len()];
+
+        for e in self.events.iter() {
+            match *e {
+                Event::Ante { seat, amount } |
+                Event::Blind { seat, amount } |
+                Event::Act { seat, amount, .. } => net[seat] -= amount as i64,
+                Event::Refund { seat, amount } |
+                Event::Win { seat, amount } => net[seat] += amount as i64,
+                _ => (),
+            }
+        }
+        net
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Player) | One player's state in a hand
+#[derive(Debug, Clone, Copy)]
+pub struct Player {
+    /// Chips not yet bet
+    pub stack: Chips,
+    /// Chips bet on this street
+    pub bet: Chips,
+    /// Chips put in the pot this hand, including antes
+    pub total: Chips,
+    /// Cards face down
+    pub down: Hand,
+    /// Cards face up
+    pub up: Hand,
+    /// Has the player folded?
+    pub folded: bool,
+    // Has the player acted voluntarily this round?
+    acted: bool,
+    // Bet the player last acted on, to tell if the betting was reopened
+    faced: Chips,
+}
+
+impl Player {
+    fn new(stack: Chips, t: DeckType) -> Self {
+        Player {
+            stack, bet: 0, total: 0, down: Hand::new(t), up: Hand::new(t),
+            folded: false, acted: false, faced: 0,
+        }
+    }
+
+    /// All the player's cards, down cards first
+    pub fn cards(&self) -> Hand {
+        let mut h = self.down;
+        h.push_all(self.up.iter());
+        h
+    }
+
+    /// Still in the hand with no chips left to bet?
+    pub fn is_all_in(&self) -> bool {
+        ! self.folded && 0 == self.stack
+    }
+
+    fn can_act(&self) -> bool {
+        ! self.folded && self.stack > 0
+    }
+
+    // Put chips (as many as the stack covers) in the pot, returning how
+    // many
+    fn put(&mut self, n: Chips) -> Chips {
+        let n = n.min(self.stack);
+        self.stack -= n;
+        self.total += n;
+        n
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Engine) | State machine for one hand
+///
+/// Create it with the starting stacks, then feed it the action of each
+/// player in turn with [Engine::act], and discards with [Engine::draw] in
+/// draw games, until [Engine::phase] is [Phase::Complete].
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::*;
+///
+/// let deck = Deck::new(DeckType::English).reproducible(17).shuffled();
+/// let holdem = GameRules::by_name("holdem").unwrap();
+/// let mut e = Engine::new(holdem, Stakes::no_limit(1, 2),
+///     &[100, 100, 100], 0, deck).unwrap();
+///
+/// assert_eq!(e.phase(), Phase::Betting(0));     // under the gun
+/// let legal = e.legal_actions().unwrap();
+/// assert_eq!(legal.call, Some(2));
+/// assert_eq!(legal.raise, Some((4, 100)));
+/// e.act(Action::Raise(6)).unwrap();
+/// e.act(Action::Fold).unwrap();
+/// e.act(Action::Call).unwrap();
+///
+/// while let Phase::Betting(_) = e.phase() {
+///     e.act(Action::Check).unwrap();
+/// }
+/// assert_eq!(e.board().len(), 5);
+/// assert_eq!(e.record().net().iter().sum::<i64>(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Engine {
+    rules: GameRules,
+    stakes: Stakes,
+    deck: Deck,
+    players: Vec<Player>,
+    board: Hand,
+    button: usize,
+    street: usize,
+    phase: Phase,
+    // Total bet each player must match this round
+    to_match: Chips,
+    // Size of the last full bet or raise this round
+    last_raise: Chips,
+    // Bets and raises this round, for the fixed-limit cap
+    raises: usize,
+    // Seat that must bring in or complete
+    bring_in: Option<usize>,
+    // Discards and folded cards
+    muck: Vec<Card>,
+    // Recorded reshuffles to use instead of shuffling, when replaying
+    reshuffles: VecDeque<Vec<Card>>,
+    record: HandRecord,
+}
+
+impl Engine {
+    /// Deal a new hand, posting forced bets and dealing the first street
+    pub fn new(rules: &GameRules, stakes: Stakes, stacks: &[Chips],
+    button: usize, deck: Deck) -> Result<Engine> {
+        let mut e = Engine::setup(rules, stakes, stacks, button, deck)?;
+        e.start()?;
+        Ok(e)
+    }
+
+    /// Deal a new hand of a built-in game from a freshly shuffled deck
+    pub fn by_name(name: &str, stakes: Stakes, stacks: &[Chips], button: usize)
+    -> Result<Engine> {
+        let rules = GameRules::by_name(name).ok_or_else(||
+            Error::BadGame(format!("unknown game {}", name)))?;
+        Engine::new(rules, stakes, stacks, button, rules.new_deck().shuffled())
+    }
+
+    /// Replay a recorded hand, checking that it plays out the same way
+    pub fn replay(rules: &GameRules, record: &HandRecord) -> Result<Engine> {
+        let mut deck = rules.new_deck();
+        let _ = deck.pop_all();
+        deck.push_all(record.deck.iter().copied());
+
+        let mut e = Engine::setup(rules, record.stakes, &record.stacks,
+            record.button, deck)?;
+        e.reshuffles = record.events.iter().filter_map(|ev| match ev {
+            Event::Reshuffle(cards) => Some(cards.clone()),
+            _ => None,
+        }).collect();
+        e.start()?;
+
+        for ev in record.events.iter() {
+            match ev {
+                Event::Act { action, .. } => e.act(*action)?,
+                Event::Draw { discards, .. } => e.draw(discards)?,
+                _ => (),
+            }
+        }
+        if e.record.events != record.events {
+            return Err(Error::BadGame("replay doesn't match record".into()));
+        }
+        Ok(e)
+    }
+
+    /// Rules of the game being played
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    /// Forced bets and bet sizes
+    pub fn stakes(&self) -> Stakes {
+        self.stakes
+    }
+
+    /// What the engine is waiting for
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Is the hand over?
+    pub fn is_complete(&self) -> bool {
+        Phase::Complete == self.phase
+    }
+
+    /// Current street, counting from 0
+    pub fn street(&self) -> usize {
+        self.street
+    }
+
+    /// Seat of the dealer button
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    /// State of each player
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Community cards
+    pub fn board(&self) -> &Hand {
+        &self.board
+    }
+
+    /// Chips in the pot, including bets on this street
+    pub fn pot(&self) -> Chips {
+        if self.is_complete() { return 0; }
+        self.players.iter().map(|p| p.total).sum()
+    }
+
+    /// Total bet each player must match this round
+    pub fn to_match(&self) -> Chips {
+        self.to_match
+    }
+
+    /// Record of the hand so far
+    pub fn record(&self) -> &HandRecord {
+        &self.record
+    }
+
+    /// Take the record of the hand
+    pub fn into_record(self) -> HandRecord {
+        self.record
+    }
+
+    /// Actions allowed to the player to act, if betting
+    pub fn legal_actions(&self) -> Option<LegalActions> {
+        let Phase::Betting(i) = self.phase else {
+            return None;
+        };
+        let p = &self.players[i];
+
+        if Some(i) == self.bring_in {
+            return Some(LegalActions {
+                seat: i, fold: false, check: false, call: None,
+                bring_in: Some(self.stakes.bring_in.min(p.stack)),
+                bet: Some(self.bet_range(i)), raise: None,
+            });
+        }
+        let owe = self.to_match.saturating_sub(p.bet);
+        let range = if self.can_raise(i) { Some(self.bet_range(i)) } else { None };
+        let opening = 0 == self.to_match;
+
+        Some(LegalActions {
+            seat: i, fold: true, check: 0 == owe,
+            call: if owe > 0 { Some(owe.min(p.stack)) } else { None },
+            bring_in: None,
+            bet: if opening { range } else { None },
+            raise: if opening { None } else { range },
+        })
+    }
+
+    /// Take the betting action of the player to act
+    pub fn act(&mut self, a: Action) -> Result<()> {
+        let Some(legal) = self.legal_actions() else {
+            return Err(Error::IllegalAction(format!("{:?} when not betting", a)));
+        };
+        let i = legal.seat;
+        if ! legal.allows(a) {
+            return Err(Error::IllegalAction(format!("{:?} by seat {}", a, i)));
+        }
+        let before = self.players[i].stack;
+        let (min_to, size) = (self.min_raise_to(), self.bet_size());
+        let p = &mut self.players[i];
+
+        match a {
+            Action::Fold => {
+                p.folded = true;
+                self.muck.extend(p.cards().iter());
+                p.down.clear();
+                p.up.clear();
+            },
+            Action::Check => (),
+            Action::Call => {
+                p.bet += p.put(self.to_match - p.bet);
+            },
+            Action::BringIn => {
+                p.bet += p.put(self.stakes.bring_in);
+                self.to_match = self.to_match.max(p.bet);
+            },
+            Action::Bet(to) | Action::Raise(to) => {
+                p.bet += p.put(to - p.bet);
+                if to >= min_to {
+                    self.last_raise = (to - self.to_match).max(size);
+                }
+                self.to_match = to;
+                self.raises += 1;
+            },
+        }
+        // Bringing in closes the action like a call, but leaves the
+        // player free to raise a completion
+        let p = &mut self.players[i];
+        p.acted = true;
+        p.faced = if Action::BringIn == a { 0 } else { self.to_match };
+        self.bring_in = None;
+
+        let amount = before - p.stack;
+        self.record.events.push(Event::Act { seat: i, action: a, amount });
+
+        if 1 == self.live_count() {
+            return self.finish();
+        }
+        self.advance_from(i + 1)
+    }
+
+    /// Replace the given cards of the player to draw, which may be none
+    /// to stand pat
+    pub fn draw(&mut self, discards: &[Card]) -> Result<()> {
+        let Phase::Drawing(i) = self.phase else {
+            return Err(Error::IllegalAction("draw when not drawing".into()));
+        };
+        let most = self.rules.streets[self.street].draw;
+        if discards.len() > most {
+            return Err(Error::IllegalAction(format!("seat {} drawing {} of {}",
+                i, discards.len(), most)));
+        }
+        for (k, &c) in discards.iter().enumerate() {
+            if discards[..k].contains(&c) {
+                return Err(Error::DuplicateCard(c));
+            }
+            if ! self.players[i].down.contains(c) {
+                return Err(Error::CardNotFound(c));
+            }
+        }
+        let cards = self.take_drawing(discards.len())?;
+        let p = &mut self.players[i];
+
+        for &c in discards {
+            p.down.remove_card(c);
+        }
+        p.down.push_all(cards.iter().copied());
+        self.muck.extend_from_slice(discards);
+        self.record.events.push(Event::Draw {
+            seat: i, discards: discards.to_vec(), cards,
+        });
+
+        let next = self.live_seats().skip_while(|&j| j != i).nth(1);
+        match next {
+            Some(j) => {
+                self.phase = Phase::Drawing(j);
+                Ok(())
+            },
+            None => self.begin_betting(),
+        }
+    }
+
+    fn setup(rules: &GameRules, stakes: Stakes, stacks: &[Chips],
+    button: usize, deck: Deck) -> Result<Engine> {
+        rules.validate()?;
+        let bad = |msg: &str| Err(Error::BadGame(msg.into()));
+
+        if stacks.len() < 2 {
+            return bad("need at least two players");
+        }
+        if stacks.contains(&0) {
+            return bad("every player needs chips");
+        }
+        if button >= stacks.len() {
+            return bad("button not at a seat");
+        }
+        if deck.deck_type() != rules.deck {
+            return bad("wrong deck for game");
+        }
+        if 0 == stakes.small_bet || stakes.big_bet < stakes.small_bet {
+            return bad("bet sizes out of order");
+        }
+        let first = rules.streets[0];
+        if first.up > 0 && stakes.bring_in > stakes.small_bet {
+            return bad("bring-in more than a bet");
+        }
+        if 0 == first.up && stakes.small_blind > stakes.big_blind {
+            return bad("small blind more than big blind");
+        }
+        if stacks.len() * first.cards_per_player() + first.board > deck.len() {
+            return bad("not enough cards to deal");
+        }
+        // Draws reshuffle the muck, and stud falls back to a community
+        // card, but anything else has to fit the whole deal in the deck.
+        if 0 == rules.draws() && 0 == first.up &&
+            stacks.len() * rules.hole_cards() + rules.board_cards() > deck.len() {
+            return bad("not enough cards to deal");
+        }
+        let t = rules.deck;
+
+        Ok(Engine {
+            rules: rules.clone(),
+            stakes,
+            players: stacks.iter().map(|&s| Player::new(s, t)).collect(),
+            board: Hand::new(t),
+            button,
+            street: 0,
+            phase: Phase::Complete,
+            to_match: 0,
+            last_raise: 0,
+            raises: 0,
+            bring_in: None,
+            muck: Vec::new(),
+            reshuffles: VecDeque::new(),
+            record: HandRecord {
+                rules: rules.clone(),
+                stakes,
+                stacks: stacks.to_vec(),
+                button,
+                deck: deck.to_vec(),
+                events: Vec::new(),
+            },
+            deck,
+        })
+    }
+
+    // Post antes and blinds and deal the first street
+    fn start(&mut self) -> Result<()> {
+        for i in self.seats_from(self.button + 1) {
+            if self.stakes.ante > 0 {
+                let amount = self.players[i].put(self.stakes.ante);
+                self.record.events.push(Event::Ante { seat: i, amount });
+            }
+        }
+        if ! self.is_stud() {
+            let (sb, bb) = self.blind_seats();
+
+            for (i, blind) in [(sb, self.stakes.small_blind), (bb, self.stakes.big_blind)] {
+                if blind > 0 {
+                    let p = &mut self.players[i];
+                    let amount = p.put(blind);
+                    p.bet += amount;
+                    self.record.events.push(Event::Blind { seat: i, amount });
+                }
+            }
+            self.to_match = self.players[sb].bet.max(self.players[bb].bet);
+            self.raises = if self.to_match > 0 { 1 } else { 0 };
+        }
+        self.begin_street()
+    }
+
+    // Deal the current street and start its draw or betting
+    fn begin_street(&mut self) -> Result<()> {
+        let s = self.rules.streets[self.street];
+        self.record.events.push(Event::Street(self.street));
+
+        let live: Vec<usize> = self.live_seats().collect();
+        let per = s.cards_per_player();
+
+        // Stud rule: if there aren't enough cards to go around, deal them
+        // to the board instead
+        if per > 0 && 0 == s.board && self.deck.len() < live.len() * per {
+            self.deal_board(per)?;
+        } else if per > 0 {
+            let mut dealt = vec![(Vec::new(), Vec::new()); live.len()];
+
+            for k in 0..per {
+                for (j, &i) in live.iter().enumerate() {
+                    let c = self.take(1)?[0];
+
+                    if k < s.down {
+                        self.players[i].down.push(c);
+                        dealt[j].0.push(c);
+                    } else {
+                        self.players[i].up.push(c);
+                        dealt[j].1.push(c);
+                    }
+                }
+            }
+            for (&seat, (down, up)) in live.iter().zip(dealt) {
+                self.record.events.push(Event::Deal { seat, down, up });
+            }
+        }
+        if s.board > 0 {
+            self.deal_board(s.board)?;
+        }
+        if s.draw > 0 {
+            self.phase = Phase::Drawing(live[0]);
+            return Ok(());
+        }
+        self.begin_betting()
+    }
+
+    fn deal_board(&mut self, n: usize) -> Result<()> {
+        let cards = self.take(n)?;
+        self.board.push_all(cards.iter().copied());
+        self.record.events.push(Event::Board(cards));
+        Ok(())
+    }
+
+    // Set up a betting round and find the first player to act
+    fn begin_betting(&mut self) -> Result<()> {
+        if self.street > 0 {
+            for p in self.players.iter_mut() {
+                p.bet = 0;
+            }
+            self.to_match = 0;
+            self.raises = 0;
+        }
+        for p in self.players.iter_mut() {
+            p.acted = false;
+            p.faced = 0;
+        }
+        self.last_raise = self.bet_size();
+
+        let first = if self.is_stud() {
+            let live = self.live_seats();
+
+            if 0 == self.street {
+                let low = self.rules.scale.low_hands();
+                let card = |i: usize| self.players[i].up[0];
+                let seat = if low {
+                    live.max_by_key(|&i| card(i))
+                } else {
+                    live.min_by_key(|&i| card(i))
+                }.expect("no live players");
+
+                if self.stakes.bring_in > 0 && self.players[seat].can_act() {
+                    self.bring_in = Some(seat);
+                }
+                seat
+            } else {
+                let g = self.rules.scale;
+                live.min_by_key(|&i| g.value(&self.players[i].up))
+                    .expect("no live players")
+            }
+        } else if 0 == self.street {
+            self.blind_seats().1 + 1
+        } else {
+            self.button + 1
+        };
+        self.advance_from(first)
+    }
+
+    // Give the action to the first player from seat `i` on who needs to
+    // act, or end the round if there isn't one
+    fn advance_from(&mut self, i: usize) -> Result<()> {
+        match self.seats_from(i).find(|&j| self.needs_action(j)) {
+            Some(j) => {
+                self.phase = Phase::Betting(j);
+                Ok(())
+            },
+            None if self.street + 1 < self.rules.streets.len() => {
+                self.street += 1;
+                self.begin_street()
+            },
+            None => self.finish(),
+        }
+    }
+
+    fn needs_action(&self, i: usize) -> bool {
+        let p = &self.players[i];
+
+        if ! p.can_act() { return false; }
+        if Some(i) == self.bring_in { return true; }
+        if p.bet < self.to_match { return true; }
+        ! p.acted && self.others_can_act(i)
+    }
+
+    fn can_raise(&self, i: usize) -> bool {
+        let p = &self.players[i];
+
+        if p.stack <= self.to_match.saturating_sub(p.bet) ||
+            ! self.others_can_act(i) {
+            return false;
+        }
+        if Limit::FixedLimit == self.stakes.limit && self.stakes.cap > 0 &&
+            self.raises >= self.stakes.cap && self.live_count() > 2 {
+            return false;
+        }
+        ! p.acted || self.to_match - p.faced >= self.last_raise
+    }
+
+    fn min_raise_to(&self) -> Chips {
+        let size = self.bet_size();
+
+        if self.to_match < size { size } else { self.to_match + self.last_raise }
+    }
+
+    // Smallest and largest totals player `i` may bet or raise to
+    fn bet_range(&self, i: usize) -> (Chips, Chips) {
+        let p = &self.players[i];
+        let all_in = p.bet + p.stack;
+        let min = self.min_raise_to();
+
+        let max = match self.stakes.limit {
+            Limit::NoLimit => all_in,
+            Limit::PotLimit => min.max(self.to_match + self.pot() +
+                self.to_match.saturating_sub(p.bet)),
+            Limit::FixedLimit => min,
+        };
+        (min.min(all_in), max.min(all_in))
+    }
+
+    fn bet_size(&self) -> Chips {
+        self.stakes.bet_size(self.street, self.rules.streets.len())
+    }
+
+    // Refund any uncalled bet, show down if needed, and award the pots
+    fn finish(&mut self) -> Result<()> {
+        let mut totals: Vec<Chips> = self.players.iter().map(|p| p.total).collect();
+        totals.sort_unstable();
+        let (top, second) = (totals[totals.len() - 1], totals[totals.len() - 2]);
+
+        if top > second {
+            let i = self.players.iter().position(|p| p.total == top).unwrap();
+            let p = &mut self.players[i];
+            p.total = second;
+            p.stack += top - second;
+            self.record.events.push(Event::Refund { seat: i, amount: top - second });
+        }
+        let live: Vec<usize> = self.live_seats().collect();
+        let mut values = vec![(HAND_VALUE_WORST, None); self.players.len()];
+
+        if live.len() > 1 {
+            let split = self.rules.split_rules();
+
+            for &i in live.iter() {
+                let hole = self.players[i].cards();
+                let (high, low) = match split {
+                    Some(r) => {
+                        let d = ojp_split_description(&hole, &self.board, r)?;
+                        (d.high.value, d.low.map(|d| d.value))
+                    },
+                    None => (ojp_best_value(&hole, &self.board,
+                        self.rules.scale, self.rules.usage)?, None),
+                };
+                values[i] = (high, low);
+                self.record.events.push(Event::Show { seat: i, high, low });
+            }
+        }
+        self.award(&live, &values);
+        self.phase = Phase::Complete;
+        Ok(())
+    }
+
+    // Split main and side pots among the live players who are eligible
+    // for each, odd chips going to the first winners after the button
+    fn award(&mut self, live: &[usize], values: &[(HandValue, Option<HandValue>)]) {
+        let mut levels: Vec<Chips> = live.iter().map(|&i| self.players[i].total).collect();
+        levels.sort_unstable();
+        levels.dedup();
+        let mut prev = 0;
+
+        for (k, &level) in levels.iter().enumerate() {
+            // Top pot also takes anything folded players put in above it
+            let cap = if k + 1 == levels.len() { Chips::MAX } else { level };
+            let pot: Chips = self.players.iter()
+                .map(|p| p.total.min(cap).saturating_sub(prev)).sum();
+            let eligible: Vec<usize> = live.iter().copied()
+                .filter(|&i| self.players[i].total >= level).collect();
+            prev = level;
+
+            let high = best_of(&eligible, |i| Some(values[i].0));
+            let low = best_of(&eligible, |i| values[i].1);
+
+            if low.is_empty() {
+                self.pay(&high, pot);
+            } else {
+                self.pay(&high, pot - pot / 2);
+                self.pay(&low, pot / 2);
+            }
+        }
+    }
+
+    fn pay(&mut self, winners: &[usize], pot: Chips) {
+        let n = winners.len() as Chips;
+
+        for (k, &i) in winners.iter().enumerate() {
+            let amount = pot / n + if (k as Chips) < pot % n { 1 } else { 0 };
+            if amount > 0 {
+                self.players[i].stack += amount;
+                self.record.events.push(Event::Win { seat: i, amount });
+            }
+        }
+    }
+
+    // Take cards from the top of the deck
+    fn take(&mut self, n: usize) -> Result<Vec<Card>> {
+        if self.deck.len() < n {
+            return Err(Error::EmptyDeck(self.deck.len(), n));
+        }
+        Ok(self.deck.draw(n).collect())
+    }
+
+    // Take cards for a draw, shuffling the muck under what's left of the
+    // deck if needed
+    fn take_drawing(&mut self, n: usize) -> Result<Vec<Card>> {
+        if self.deck.len() < n && ! self.muck.is_empty() {
+            let top: Vec<Card> = self.deck.pop_all().collect();
+            self.deck.push_all(self.muck.drain(..));
+
+            match self.reshuffles.pop_front() {
+                Some(order) => {
+                    let _ = self.deck.pop_all();
+                    self.deck.push_all(order);
+                },
+                None => self.deck.shuffle(),
+            }
+            self.record.events.push(Event::Reshuffle(self.deck.to_vec()));
+            self.deck.push_all(top);
+        }
+        self.take(n)
+    }
+
+    fn is_stud(&self) -> bool {
+        self.rules.streets[0].up > 0
+    }
+
+    fn blind_seats(&self) -> (usize, usize) {
+        let n = self.players.len();
+
+        if 2 == n {
+            (self.button, (self.button + 1) % n)
+        } else {
+            ((self.button + 1) % n, (self.button + 2) % n)
+        }
+    }
+
+    // Every seat once, starting with `i`
+    fn seats_from(&self, i: usize) -> impl Iterator<Item = usize> {
+        let n = self.players.len();
+        (0..n).map(move |k| (i + k) % n)
+    }
+
+    // Seats still in the hand, starting after the button
+    fn live_seats(&self) -> impl Iterator<Item = usize> + '_ {
+        self.seats_from(self.button + 1).filter(|&i| ! self.players[i].folded)
+    }
+
+    fn live_count(&self) -> usize {
+        self.players.iter().filter(|p| ! p.folded).count()
+    }
+
+    fn others_can_act(&self, i: usize) -> bool {
+        self.players.iter().enumerate().any(|(j, p)| j != i && p.can_act())
+    }
+}
+
+// Seats with the best value, in the order given
+fn best_of<F>(seats: &[usize], value: F) -> Vec<usize>
+where F: Fn(usize) -> Option<HandValue> {
+    let Some(best) = seats.iter().filter_map(|&i| value(i)).min() else {
+        return Vec::new();
+    };
+    seats.iter().copied().filter(|&i| Some(best) == value(i)).collect()
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Random;
+
+    // Deck of the given type that deals the given cards first
+    fn stacked(t: DeckType, cards: &[Card]) -> Deck {
+        let mut deck = Deck::new(t).reproducible(1).shuffled();
+        for &c in cards {
+            deck.remove_card(c);
+        }
+        deck.push_all(cards.iter().rev().copied());
+        deck
+    }
+
+    #[test]
+    fn test_engine_side_pots() -> Result<()> {
+        let holdem = GameRules::by_name("holdem").unwrap();
+        let deck = stacked(DeckType::English, &hand!("Ks","7c","As",
+            "Kh","2d","Ah", "9c","8d","4s","3h","Jc"));
+        let mut e = Engine::new(holdem, Stakes::no_limit(1, 2),
+            &[50, 100, 200], 0, deck)?;
+
+        assert_eq!(e.players()[0].down.to_string(), "AsAh");
+        assert_eq!(e.pot(), 3);
+        let legal = e.legal_actions().unwrap();
+        assert_eq!(legal.seat, 0);
+        assert!(! legal.check && legal.bet.is_none());
+        assert!(e.act(Action::Raise(3)).is_err());
+        assert!(e.act(Action::Bet(10)).is_err());
+        e.act(Action::Raise(50))?;
+
+        let legal = e.legal_actions().unwrap();
+        assert_eq!((legal.seat, legal.call, legal.raise), (1, Some(49), Some((98, 100))));
+        e.act(Action::Raise(100))?;
+        assert_eq!(e.legal_actions().unwrap().raise, None);
+        e.act(Action::Call)?;
+
+        assert!(e.is_complete());
+        assert_eq!(e.board().to_string(), "9c8d4s3hJc");
+        let stacks: Vec<Chips> = e.players().iter().map(|p| p.stack).collect();
+        assert_eq!(stacks, vec![150, 100, 100]);
+        assert_eq!(e.record().net(), vec![100, 0, -100]);
+        assert!(e.act(Action::Check).is_err());
+
+        // Uncalled bet comes back
+        let deck = Deck::new(DeckType::English).reproducible(2).shuffled();
+        let mut e = Engine::new(holdem, Stakes::no_limit(1, 2), &[50, 100], 1, deck)?;
+        assert_eq!(e.legal_actions().unwrap().seat, 1);   // button is small blind
+        e.act(Action::Raise(30))?;
+        e.act(Action::Raise(50))?;
+        e.act(Action::Fold)?;
+        assert!(e.is_complete());
+        assert_eq!(e.record().net(), vec![30, -30]);
+        assert!(e.record().events.contains(&Event::Refund { seat: 0, amount: 20 }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_engine_limits() -> Result<()> {
+        // Pot-limit raise before the flop is to seven
+        let omaha = GameRules::by_name("omaha").unwrap();
+        let e = Engine::new(omaha, Stakes::pot_limit(1, 2), &[100; 4], 0,
+            omaha.new_deck().shuffled())?;
+        assert_eq!(e.legal_actions().unwrap().raise, Some((4, 7)));
+
+        // Fixed-limit razz: bring-in, completion, and the cap
+        let razz = GameRules::by_name("razz").unwrap();
+        let stakes = Stakes::fixed_limit(4, 8).ante(1).bring_in(2).cap(3);
+        let deck = stacked(DeckType::Low, &hand!("2c","3c","4c", "5c","6c","7c",
+            "Kc","5d","Ks", "Qc","2d","Qd"));
+        let mut e = Engine::new(razz, stakes, &[100; 3], 0, deck)?;
+        assert_eq!(e.pot(), 3);
+
+        let legal = e.legal_actions().unwrap();
+        assert_eq!((legal.seat, legal.fold), (0, false));
+        assert_eq!((legal.bring_in, legal.bet), (Some(2), Some((4, 4))));
+        e.act(Action::BringIn)?;
+        let legal = e.legal_actions().unwrap();
+        assert_eq!((legal.seat, legal.call, legal.raise), (1, Some(2), Some((4, 4))));
+        e.act(Action::Raise(4))?;
+        assert_eq!(e.legal_actions().unwrap().raise, Some((8, 8)));
+        e.act(Action::Raise(8))?;
+        assert_eq!(e.legal_actions().unwrap().raise, Some((12, 12)));
+        e.act(Action::Raise(12))?;
+        assert_eq!(e.legal_actions().unwrap().raise, None);     // capped
+        e.act(Action::Call)?;
+        e.act(Action::Call)?;
+
+        // Best low showing acts first
+        assert_eq!(e.street(), 1);
+        assert_eq!(e.phase(), Phase::Betting(2));
+        e.act(Action::Bet(4))?;
+        e.act(Action::Fold)?;
+        e.act(Action::Fold)?;
+        assert!(e.is_complete());
+        assert_eq!(e.record().net(), vec![-13, -13, 26]);
+        Ok(())
+    }
+
+    // Play random legal actions until the hand is over
+    fn play_randomly(e: &mut Engine, rng: &mut Random) -> Result<()> {
+        loop {
+            match e.phase() {
+                Phase::Betting(_) => {
+                    let legal = e.legal_actions().unwrap();
+                    let mut choices = Vec::new();
+                    if legal.fold && rng.uniform16(4) == 0 { choices.push(Action::Fold); }
+                    if legal.check { choices.push(Action::Check); }
+                    if legal.call.is_some() { choices.push(Action::Call); }
+                    if legal.bring_in.is_some() { choices.push(Action::BringIn); }
+                    if let Some((lo, hi)) = legal.bet {
+                        choices.push(Action::Bet(lo + (hi - lo) / 3));
+                    }
+                    if let Some((lo, hi)) = legal.raise {
+                        choices.push(Action::Raise(if rng.uniform16(2) == 0 { lo } else { hi }));
+                    }
+                    e.act(choices[rng.uniform16(choices.len())])?;
+                },
+                Phase::Drawing(i) => {
+                    let most = e.rules().streets[e.street()].draw;
+                    let n = rng.uniform16(most + 1);
+                    let discards: Vec<Card> = e.players()[i].down.iter().take(n).collect();
+                    e.draw(&discards)?;
+                },
+                Phase::Complete => return Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_games() -> Result<()> {
+        let mut rng = Random::new().seeded(25);
+
+        for g in GameRegistry::builtin().iter() {
+            let stakes = match g.name.as_str() {
+                "razz" | "stud-8" | "seven-card-stud" | "five-card-stud" =>
+                    Stakes::fixed_limit(4, 8).ante(1).bring_in(2),
+                "omaha" | "omaha-8" | "courchevel" => Stakes::pot_limit(1, 2).ante(1),
+                _ => Stakes::no_limit(1, 2),
+            };
+            let seats: usize = if g.streets[0].up > 0 { 8 } else { 6 };
+
+            for seed in 0..20 {
+                let stacks: Vec<Chips> = (0..seats).map(|i| 40 + 30 * i as Chips).collect();
+                let deck = g.new_deck().reproducible(seed).shuffled();
+                let mut e = Engine::new(g, stakes, &stacks, seed as usize % seats, deck)?;
+                play_randomly(&mut e, &mut rng)?;
+
+                let end: Chips = e.players().iter().map(|p| p.stack).sum();
+                assert_eq!(end, stacks.iter().sum());
+                let record = e.into_record();
+                assert_eq!(record.net().iter().sum::<i64>(), 0);
+
+                let again = record.replay()?;
+                assert!(again.is_complete());
+                assert_eq!(again.record(), &record);
+
+                #[cfg(feature = "serde")]
+                {
+                    let text = serde_json5::to_string(&record)?;
+                    let back: HandRecord = serde_json5::from_str(&text)?;
+                    assert_eq!(back, record);
+                }
+            }
+        }
+        // Game built in code, not in any registry
+        let mine = GameRules::new("double-draw-27", Scale::DeuceToSeven)
+            .streets(&[Street::new().down(5), Street::new().draw(5),
+                Street::new().draw(5)]);
+        assert!(GameRules::by_name(&mine.name).is_none());
+        let deck = mine.new_deck().reproducible(99).shuffled();
+        let mut e = Engine::new(&mine, Stakes::fixed_limit(2, 4), &[50; 4], 1, deck)?;
+        play_randomly(&mut e, &mut rng)?;
+        let record = e.into_record();
+        assert_eq!(record.rules, mine);
+        assert_eq!(record.replay()?.record(), &record);
+        #[cfg(feature = "serde")]
+        {
+            let text = serde_json5::to_string(&record)?;
+            let back: HandRecord = serde_json5::from_str(&text)?;
+            assert_eq!(back.replay()?.record(), &record);
+        }
+
+        let mut bad = Engine::by_name("holdem", Stakes::no_limit(1, 2), &[10, 10], 0)?
+            .into_record();
+        bad.events.push(Event::Act { seat: 0, action: Action::Raise(7), amount: 5 });
+        assert!(bad.replay().is_err());
+
+        assert!(Engine::by_name("holdem", Stakes::no_limit(1, 2), &[10], 0).is_err());
+        assert!(Engine::by_name("holdem", Stakes::no_limit(1, 2), &[10, 0], 0).is_err());
+        assert!(Engine::by_name("holdem", Stakes::no_limit(1, 2), &[10; 30], 0).is_err());
+        assert!(Engine::by_name("holdem", Stakes::no_limit(1, 2), &[100; 25], 0).is_err());
+        assert!(Engine::by_name("holdem", Stakes::no_limit(1, 2), &[100; 23], 0).is_ok());
+        assert!(Engine::by_name("go-fish", Stakes::no_limit(1, 2), &[10, 10], 0).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,907 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Equity) | Pot equity calculation
+
+use crate::error::{Error, Result};
+use crate::cards::*;
+use crate::utils::{Random, oj_binomial};
+use crate::poker::*;
+use crate::poker::best_hand::hole_counts;
+use crate::poker::range::mask_of;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/EquityMode) | How to deal the unknown cards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquityMode {
+    /// Deal every possible combination of unknown cards
+    Exhaustive,
+    /// Deal a random sample of combinations, reproducible from the seed
+    MonteCarlo {
+        /// Number of deals to sample
+        trials: u64,
+        /// PRNG seed
+        seed: u64,
+    },
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/EquityGame) | Shape of the deal to evaluate
+///
+/// Final sizes of the board and of each player's hand, and how the hole
+/// cards combine with the board. Sizes left as `None` are inferred from the cards given:
+/// each player's hand is filled to the largest hand given, and the board
+/// is filled to a complete hand if there is one or if the hands are short.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{EquityGame, HoleUsage};
+///
+/// let omaha = EquityGame::new(Scale::HighHand).usage(HoleUsage::OMAHA);
+/// let stud = EquityGame::new(Scale::HighHand).board_size(0).hand_size(7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EquityGame {
+    /// Scale used to evaluate each player's final hand
+    pub scale: Scale,
+    /// Final board size
+    pub board_size: Option<usize>,
+    /// Final size of each player's hand
+    pub hand_size: Option<usize>,
+    /// How many hole cards must play
+    pub usage: HoleUsage,
+}
+
+impl EquityGame {
+    /// New game using the given scale, with sizes inferred from cards
+    pub fn new(scale: Scale) -> Self {
+        EquityGame {
+            scale, board_size: None, hand_size: None, usage: HoleUsage::Any,
+        }
+    }
+
+    /// Set final board size
+    pub fn board_size(mut self, n: usize) -> Self {
+        self.board_size = Some(n);
+        self
+    }
+
+    /// Set final size of each player's hand
+    pub fn hand_size(mut self, n: usize) -> Self {
+        self.hand_size = Some(n);
+        self
+    }
+
+    /// Set rule for how many hole cards must play
+    pub fn usage(mut self, rule: HoleUsage) -> Self {
+        self.usage = rule;
+        self
+    }
+}
+
+impl From<Scale> for EquityGame {
+    fn from(scale: Scale) -> Self {
+        EquityGame::new(scale)
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/PlayerEquity) | Results for one player
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerEquity {
+    /// Number of deals won outright
+    pub wins: u64,
+    /// Number of deals split with others
+    pub ties: u64,
+    /// Fraction of deals won outright
+    pub win: f64,
+    /// Fraction of deals split with others
+    pub tie: f64,
+    /// Expected fraction of the pot, counting splits
+    pub share: f64,
+    /// Half-width of 95% confidence interval for `share` (0 if exhaustive)
+    pub margin: f64,
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/EquityResult) | Results of equity calculation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EquityResult {
+    /// Number of deals evaluated
+    pub deals: u64,
+    /// Were all possible deals evaluated?
+    pub exhaustive: bool,
+    /// Results for each player, in order given
+    pub players: Vec<PlayerEquity>,
+}
+
+// Validated inputs and the cards that remain to be dealt
+struct Setup {
+    scale: Scale,
+    board: Hand,
+    hands: Vec<Hand>,
+    needs: Vec<usize>,
+    usage: HoleUsage,
+    stub: Deck,
+}
+
+impl Setup {
+    fn new(players: &[Hand], board: &Hand, dead: &Hand, game: EquityGame)
+    -> Result<Setup> {
+        let scale = game.scale;
+        let dt = scale.deck_type();
+        let mut stub = Deck::new(dt);
+
+        if players.len() < 2 {
+            return Err(Error::BadHand("need at least two players".into()));
+        }
+        let hands: Vec<Hand> = players.iter()
+            .map(|h| h.convert_decktype(dt)).collect();
+        let board = board.convert_decktype(dt);
+        let dead = dead.convert_decktype(dt);
+
+        for h in hands.iter().chain([&board, &dead]) {
+            for c in h.iter() {
+                if ! stub.remove_card(c) {
+                    if dt.has(c) {
+                        return Err(Error::DuplicateCard(c));
+                    }
+                    return Err(Error::InvalidCard(c, dt.name().into()));
+                }
+            }
+        }
+        let hand_size = game.hand_size.unwrap_or_else(||
+            hands.iter().map(|h| h.len()).max().unwrap_or(0));
+        let board_size = game.board_size.unwrap_or(
+            if board.is_not_empty() || hand_size < scale.complete_hand() {
+                scale.complete_hand()
+            } else {
+                0
+            });
+
+        if board.len() > board_size {
+            return Err(Error::BadHand("board has too many cards".into()));
+        }
+        if hands.iter().any(|h| h.len() > hand_size) {
+            return Err(Error::BadHand("hand has too many cards".into()));
+        }
+        hole_counts(hand_size, board_size, scale, game.usage)?;
+
+        let mut needs = vec![board_size - board.len()];
+        needs.extend(hands.iter().map(|h| hand_size - h.len()));
+
+        let total: usize = needs.iter().sum();
+        if total > stub.len() {
+            return Err(Error::EmptyDeck(total, stub.len()));
+        }
+        Ok(Setup { scale, board, hands, needs, usage: game.usage, stub })
+    }
+
+    // Number of distinct deals, or None if it's too large to count
+    fn deal_count(&self) -> Option<u64> {
+        let mut avail = self.stub.len();
+        let mut total: u64 = 1;
+
+        for &k in self.needs.iter() {
+            if avail >= 64 { return None; }
+            total = total.checked_mul(oj_binomial(avail as i32, k as i32))?;
+            avail -= k;
+        }
+        Some(total)
+    }
+}
+
+// Running totals, each deal counted with the current weight; sum of
+// squared shares is kept for the variance
+struct Tally {
+    deals: u64,
+    weight: f64,
+    total: f64,
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    win_weights: Vec<f64>,
+    tie_weights: Vec<f64>,
+    shares: Vec<f64>,
+    squares: Vec<f64>,
+}
+
+impl Tally {
+    fn new(players: usize) -> Tally {
+        Tally {
+            deals: 0,
+            weight: 1.0,
+            total: 0.0,
+            wins: vec![0; players],
+            ties: vec![0; players],
+            win_weights: vec![0.0; players],
+            tie_weights: vec![0.0; players],
+            shares: vec![0.0; players],
+            squares: vec![0.0; players],
+        }
+    }
+
+    fn showdown(&mut self, s: &Setup, board: &Hand, hands: &[Hand]) {
+        let values: Vec<HandValue> = hands.iter()
+            .map(|h| ojp_best_value(h, board, s.scale, s.usage)
+                .expect("usage checked in setup")).collect();
+        let best = *values.iter().min().expect("at least two players");
+        let winners = values.iter().filter(|&&v| v == best).count();
+        let share = 1.0 / winners as f64;
+        let w = self.weight;
+
+        for (i, v) in values.iter().enumerate() {
+            if *v != best { continue; }
+            if 1 == winners {
+                self.wins[i] += 1;
+                self.win_weights[i] += w;
+            } else {
+                self.ties[i] += 1;
+                self.tie_weights[i] += w;
+            }
+            self.shares[i] += w * share;
+            self.squares[i] += w * share * share;
+        }
+        self.deals += 1;
+        self.total += w;
+    }
+
+    fn merge(&mut self, other: &Tally) {
+        self.deals += other.deals;
+        self.total += other.total;
+
+        for i in 0..self.wins.len() {
+            self.wins[i] += other.wins[i];
+            self.ties[i] += other.ties[i];
+            self.win_weights[i] += other.win_weights[i];
+            self.tie_weights[i] += other.tie_weights[i];
+            self.shares[i] += other.shares[i];
+            self.squares[i] += other.squares[i];
+        }
+    }
+
+    fn result(&self, exhaustive: bool) -> Vec<PlayerEquity> {
+        let n = self.deals as f64;
+        (0..self.wins.len()).map(|i| {
+            let share = self.shares[i] / self.total;
+            let margin = if exhaustive || self.deals < 2 {
+                0.0
+            } else {
+                let var = (self.squares[i] / self.total - share * share).max(0.0);
+                1.96 * (var / (n - 1.0)).sqrt()
+            };
+            PlayerEquity {
+                wins: self.wins[i],
+                ties: self.ties[i],
+                win: self.win_weights[i] / self.total,
+                tie: self.tie_weights[i] / self.total,
+                share, margin,
+            }
+        }).collect()
+    }
+}
+
+// Deal every combination of cards to slot (0 for the board, then each
+// player in turn), recursing to the next slot for each one
+fn enumerate(s: &Setup, slot: usize, deck: &Deck, board: &mut Hand,
+hands: &mut [Hand], tally: &mut Tally) {
+    if slot >= s.needs.len() {
+        tally.showdown(s, board, hands);
+        return;
+    }
+    let need = s.needs[slot];
+    if 0 == need {
+        enumerate(s, slot + 1, deck, board, hands, tally);
+        return;
+    }
+    let more = s.needs[(slot + 1)..].iter().any(|&n| n > 0);
+    let saved = if 0 == slot { *board } else { hands[slot - 1] };
+
+    for combo in deck.combinations(need) {
+        let target = if 0 == slot { &mut *board } else { &mut hands[slot - 1] };
+        target.push_all(combo.iter());
+
+        if more {
+            let mut rest = deck.clone();
+            for c in combo.iter() {
+                rest.remove_card(c);
+            }
+            enumerate(s, slot + 1, &rest, board, hands, tally);
+        } else {
+            enumerate(s, slot + 1, deck, board, hands, tally);
+        }
+        if 0 == slot { *board = saved; } else { hands[slot - 1] = saved; }
+    }
+}
+
+// Deal the unknown cards at random, from a stub that is reshuffled as we go
+fn deal_random(s: &Setup, stub: &mut [Card], rng: &mut Random)
+-> (Hand, Vec<Hand>) {
+    let mut board = s.board;
+    let mut hands = s.hands.clone();
+    let mut next = 0;
+
+    for (slot, &need) in s.needs.iter().enumerate() {
+        let target = if 0 == slot { &mut board } else { &mut hands[slot - 1] };
+
+        for _ in 0..need {
+            let j = next + rng.uniform16(stub.len() - next);
+            stub.swap(next, j);
+            target.push(stub[next]);
+            next += 1;
+        }
+    }
+    (board, hands)
+}
+
+fn monte_carlo(s: &Setup, trials: u64, seed: u64, tally: &mut Tally) {
+    let mut rng = Random::new().seeded(seed);
+    let mut stub = s.stub.to_vec();
+
+    for _ in 0..trials {
+        let (board, hands) = deal_random(s, &mut stub, &mut rng);
+        tally.showdown(s, &board, &hands);
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_equity_deals) | Count possible deals
+///
+/// Number of ways to deal the unknown cards, or `None` if too large to
+/// count. Useful for deciding between exhaustive and Monte Carlo modes.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::ojp_equity_deals;
+///
+/// let p1 = Hand::new(DeckType::English).init(hand!("As","Ks"));
+/// let p2 = Hand::new(DeckType::English).init(hand!("Qh","Qd"));
+/// let empty = Hand::new(DeckType::English);
+/// let n = ojp_equity_deals(&[p1, p2], &empty, &empty, Scale::HighHand);
+/// assert_eq!(n.unwrap(), Some(1712304));
+/// ```
+pub fn ojp_equity_deals<G>(players: &[Hand], board: &Hand, dead: &Hand, game: G)
+-> Result<Option<u64>>
+where G: Into<EquityGame> {
+    Ok(Setup::new(players, board, dead, game.into())?.deal_count())
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_equity) | Calculate pot equity
+///
+/// Deal out the unknown board and hole cards, from the deck for the game
+/// less all the known and dead cards, and tally each player's wins, ties,
+/// and share of the pot. A pot split `k` ways counts `1/k` to each winner.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_equity, EquityMode};
+///
+/// let p1 = Hand::new(DeckType::English).init(hand!("As","Ks"));
+/// let p2 = Hand::new(DeckType::English).init(hand!("Qh","Qd"));
+/// let board = Hand::new(DeckType::English).init(hand!("Kd","9h","2c"));
+/// let dead = Hand::new(DeckType::English);
+///
+/// let r = ojp_equity(&[p1, p2], &board, &dead, Scale::HighHand,
+///     EquityMode::Exhaustive).unwrap();
+/// assert_eq!(r.deals, 990);
+/// assert!(r.players[0].share > 0.89);
+/// ```
+pub fn ojp_equity<G>(players: &[Hand], board: &Hand, dead: &Hand, game: G,
+mode: EquityMode) -> Result<EquityResult>
+where G: Into<EquityGame> {
+    let s = Setup::new(players, board, dead, game.into())?;
+    let mut tally = Tally::new(players.len());
+
+    match mode {
+        EquityMode::Exhaustive => {
+            let mut board = s.board;
+            let mut hands = s.hands.clone();
+            enumerate(&s, 0, &s.stub, &mut board, &mut hands, &mut tally);
+            Ok(EquityResult {
+                deals: tally.deals, exhaustive: true, players: tally.result(true),
+            })
+        },
+        EquityMode::MonteCarlo { trials, seed } => {
+            if 0 == trials {
+                return Err(Error::BadHand("no trials".into()));
+            }
+            monte_carlo(&s, trials, seed, &mut tally);
+            Ok(EquityResult {
+                deals: tally.deals, exhaustive: false, players: tally.result(false),
+            })
+        },
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ComboEquity) | Results for one hand of a range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComboEquity {
+    /// Player's hole cards
+    pub hand: Hand,
+    /// Fraction of deals in which the player held these cards
+    pub frequency: f64,
+    /// Expected fraction of the pot when holding these cards
+    pub share: f64,
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/RangeEquityResult) | Results of range equity calculation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RangeEquityResult {
+    /// Number of deals evaluated
+    pub deals: u64,
+    /// Were all possible deals evaluated?
+    pub exhaustive: bool,
+    /// Results for each player, in order given
+    pub players: Vec<PlayerEquity>,
+    /// Results for each hand of each player's range that was dealt
+    pub combos: Vec<Vec<ComboEquity>>,
+}
+
+// Most deals of hands from ranges we're willing to count one by one
+const RANGE_COUNT_LIMIT: u64 = 1 << 24;
+
+// Most random draws of hands from ranges before giving up on finding
+// a set of hands that don't collide
+const RANGE_SAMPLE_TRIES: usize = 10_000;
+
+// Each player's range less the board and dead cards, as hands, weights,
+// and card masks
+struct RangeSetup {
+    board: Hand,
+    dead: Hand,
+    game: EquityGame,
+    combos: Vec<Vec<(Hand, f64, u64)>>,
+}
+
+impl RangeSetup {
+    fn new(ranges: &[Range], board: &Hand, dead: &Hand, game: EquityGame)
+    -> Result<RangeSetup> {
+        if ranges.len() < 2 {
+            return Err(Error::BadHand("need at least two players".into()));
+        }
+        let dt = game.scale.deck_type();
+        let gone = mask_of(board.convert_decktype(dt).iter()) |
+            mask_of(dead.convert_decktype(dt).iter());
+        let mut combos = Vec::new();
+
+        for r in ranges.iter() {
+            let mut live = Vec::new();
+
+            for (h, w) in r.combos() {
+                let h = h.convert_decktype(dt);
+
+                if let Some(c) = h.iter().find(|&c| ! dt.has(c)) {
+                    return Err(Error::InvalidCard(c, dt.name().into()));
+                }
+                let m = mask_of(h.iter());
+                if 0 == m & gone {
+                    live.push((h, w, m));
+                }
+            }
+            if live.is_empty() {
+                return Err(Error::BadHand("range has no hands left".into()));
+            }
+            combos.push(live);
+        }
+        Ok(RangeSetup { board: *board, dead: *dead, game, combos })
+    }
+
+    // Call `f` with every choice of non-colliding hands, one from each
+    // range, and its weight, until it returns false
+    fn for_each<F>(&self, f: &mut F)
+    where F: FnMut(&[usize], f64) -> bool {
+        let mut picks = Vec::with_capacity(self.combos.len());
+        self.visit(0, 0, 1.0, &mut picks, f);
+    }
+
+    fn visit<F>(&self, slot: usize, used: u64, weight: f64,
+    picks: &mut Vec<usize>, f: &mut F) -> bool
+    where F: FnMut(&[usize], f64) -> bool {
+        if slot >= self.combos.len() {
+            return f(picks, weight);
+        }
+        for (i, &(_, w, m)) in self.combos[slot].iter().enumerate() {
+            if 0 != used & m { continue; }
+
+            picks.push(i);
+            let more = self.visit(slot + 1, used | m, weight * w, picks, f);
+            picks.pop();
+            if ! more { return false; }
+        }
+        true
+    }
+
+    fn first(&self) -> Result<Vec<usize>> {
+        let mut first = None;
+        self.for_each(&mut |p, _| {
+            first = Some(p.to_vec());
+            false
+        });
+        first.ok_or_else(|| Error::BadHand("no possible deal of hands \
+            from ranges".into()))
+    }
+
+    fn setup(&self, picks: &[usize]) -> Result<Setup> {
+        let hands: Vec<Hand> = picks.iter().enumerate()
+            .map(|(i, &k)| self.combos[i][k].0).collect();
+        Setup::new(&hands, &self.board, &self.dead, self.game)
+    }
+
+    fn deal_count(&self) -> Result<Option<u64>> {
+        let each = self.setup(&self.first()?)?.deal_count();
+        let bound = self.combos.iter()
+            .try_fold(1u64, |a, c| a.checked_mul(c.len() as u64));
+
+        if bound.is_none_or(|b| b > RANGE_COUNT_LIMIT) || each.is_none() {
+            return Ok(None);
+        }
+        let mut count: u64 = 0;
+        self.for_each(&mut |_, _| {
+            count += 1;
+            true
+        });
+        Ok(count.checked_mul(each.unwrap()))
+    }
+}
+
+// Per-hand totals: weight of deals and weighted pot shares
+struct ComboTally {
+    weights: Vec<Vec<f64>>,
+    shares: Vec<Vec<f64>>,
+}
+
+impl ComboTally {
+    fn new(rs: &RangeSetup) -> ComboTally {
+        ComboTally {
+            weights: rs.combos.iter().map(|c| vec![0.0; c.len()]).collect(),
+            shares: rs.combos.iter().map(|c| vec![0.0; c.len()]).collect(),
+        }
+    }
+
+    fn add(&mut self, picks: &[usize], t: &Tally) {
+        for (i, &k) in picks.iter().enumerate() {
+            self.weights[i][k] += t.total;
+            self.shares[i][k] += t.shares[i];
+        }
+    }
+
+    fn result(&self, rs: &RangeSetup, total: f64) -> Vec<Vec<ComboEquity>> {
+        rs.combos.iter().enumerate().map(|(i, c)| {
+            c.iter().enumerate().filter(|&(k, _)| self.weights[i][k] > 0.0)
+                .map(|(k, &(hand, _, _))| ComboEquity {
+                    hand,
+                    frequency: self.weights[i][k] / total,
+                    share: self.shares[i][k] / self.weights[i][k],
+                }).collect()
+        }).collect()
+    }
+}
+
+// Choose one hand from each range in proportion to weight, retrying
+// until they don't collide
+fn sample_hands(rs: &RangeSetup, cumulative: &[Vec<f64>], rng: &mut Random)
+-> Result<Vec<usize>> {
+    let mut picks = vec![0; cumulative.len()];
+
+    for _ in 0..RANGE_SAMPLE_TRIES {
+        let mut used: u64 = 0;
+        let mut ok = true;
+
+        for (i, cum) in cumulative.iter().enumerate() {
+            let total = cum[cum.len() - 1];
+            let x = total * (rng.next64() >> 11) as f64 / (1u64 << 53) as f64;
+            let k = cum.partition_point(|&c| c <= x).min(cum.len() - 1);
+            let m = rs.combos[i][k].2;
+
+            if 0 != used & m {
+                ok = false;
+                break;
+            }
+            used |= m;
+            picks[i] = k;
+        }
+        if ok {
+            return Ok(picks);
+        }
+    }
+    Err(Error::BadHand("ranges collide too often to sample".into()))
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_range_equity_deals) | Count possible deals from ranges
+///
+/// Number of ways to choose a hand from each range and deal the unknown
+/// cards, or `None` if too large to count.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_range_equity_deals, Range};
+///
+/// let r1 = Range::parse("AK").unwrap();
+/// let r2 = Range::parse("AA").unwrap();
+/// let board = Hand::new(DeckType::English).init(hand!("Ah","7d","2s","3c"));
+/// let empty = Hand::new(DeckType::English);
+/// let n = ojp_range_equity_deals(&[r1, r2], &board, &empty, Scale::HighHand);
+/// assert_eq!(n.unwrap(), Some(12 * 44));
+/// ```
+pub fn ojp_range_equity_deals<G>(ranges: &[Range], board: &Hand, dead: &Hand,
+game: G) -> Result<Option<u64>>
+where G: Into<EquityGame> {
+    RangeSetup::new(ranges, board, dead, game.into())?.deal_count()
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_range_equity) | Calculate pot equity of ranges
+///
+/// Like [ojp_equity], but each player holds a hand from a [Range]. Hands
+/// that collide with each other, the board, or the dead cards are never
+/// dealt, and the rest are dealt in proportion to the product of their
+/// weights. Exhaustive mode visits every choice of hands and every deal of
+/// the unknown cards; Monte Carlo samples both. Use `Range::from(&hand)`
+/// for a player with a known hand.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_range_equity, EquityMode, Range};
+///
+/// let hero = Hand::new(DeckType::English).init(hand!("Qs","Qd"));
+/// let villain = Range::parse("AK, KK+").unwrap();
+/// let board = Hand::new(DeckType::English).init(hand!("Kh","7c","2d","Qh"));
+/// let empty = Hand::new(DeckType::English);
+///
+/// let r = ojp_range_equity(&[Range::from(&hero), villain], &board, &empty,
+///     Scale::HighHand, EquityMode::Exhaustive).unwrap();
+/// assert_eq!(r.combos[1].len(), 12 + 3 + 6);
+/// assert!(r.players[0].share > 0.5);
+/// ```
+pub fn ojp_range_equity<G>(ranges: &[Range], board: &Hand, dead: &Hand,
+game: G, mode: EquityMode) -> Result<RangeEquityResult>
+where G: Into<EquityGame> {
+    let rs = RangeSetup::new(ranges, board, dead, game.into())?;
+    let mut tally = Tally::new(ranges.len());
+    let mut combos = ComboTally::new(&rs);
+    let mut err: Option<Error> = None;
+    rs.first()?;
+
+    let exhaustive = match mode {
+        EquityMode::Exhaustive => {
+            rs.for_each(&mut |picks, weight| {
+                let s = match rs.setup(picks) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        err = Some(e);
+                        return false;
+                    },
+                };
+                let mut t = Tally::new(picks.len());
+                t.weight = weight;
+                let mut board = s.board;
+                let mut hands = s.hands.clone();
+                enumerate(&s, 0, &s.stub, &mut board, &mut hands, &mut t);
+
+                tally.merge(&t);
+                combos.add(picks, &t);
+                true
+            });
+            true
+        },
+        EquityMode::MonteCarlo { trials, seed } => {
+            if 0 == trials {
+                return Err(Error::BadHand("no trials".into()));
+            }
+            let mut rng = Random::new().seeded(seed);
+            let cumulative: Vec<Vec<f64>> = rs.combos.iter().map(|c|
+                c.iter().scan(0.0, |a, &(_, w, _)| {
+                    *a += w;
+                    Some(*a)
+                }).collect()).collect();
+
+            for _ in 0..trials {
+                let picks = sample_hands(&rs, &cumulative, &mut rng)?;
+                let s = rs.setup(&picks)?;
+                let mut stub = s.stub.to_vec();
+                let (board, hands) = deal_random(&s, &mut stub, &mut rng);
+
+                let mut t = Tally::new(picks.len());
+                t.showdown(&s, &board, &hands);
+                tally.merge(&t);
+                combos.add(&picks, &t);
+            }
+            false
+        },
+    };
+    if let Some(e) = err {
+        return Err(e);
+    }
+    Ok(RangeEquityResult {
+        deals: tally.deals,
+        exhaustive,
+        players: tally.result(exhaustive),
+        combos: combos.result(&rs, tally.total),
+    })
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equity() -> Result<()> {
+        let deck = Deck::new(DeckType::English);
+        let empty = deck.new_hand();
+        let p1 = deck.new_hand().init(hand!("As","Ad"));
+        let p2 = deck.new_hand().init(hand!("Ks","Kd"));
+        let p3 = deck.new_hand().init(hand!("7c","2h"));
+
+        // River: nothing left to deal
+        let board = deck.new_hand().init(hand!("2c","3d","7h","9s","Tc"));
+        let r = ojp_equity(&[p1, p2], &board, &empty, Scale::HighHand,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.deals, 1);
+        assert_eq!(r.players[0].wins, 1);
+        assert_eq!(r.players[1].share, 0.0);
+
+        // Board plays for everyone: three-way split
+        let board = deck.new_hand().init(hand!("Ah","Kh","Qh","Jh","Th"));
+        let r = ojp_equity(&[p1, p2, p3], &board, &empty, Scale::HighHand,
+            EquityMode::Exhaustive)?;
+        for p in r.players.iter() {
+            assert_eq!(p.ties, 1);
+            assert!((p.share - 1.0 / 3.0).abs() < 1e-9);
+        }
+
+        // Turn: kings need one of two outs, then one is dead
+        let board = deck.new_hand().init(hand!("2c","3d","7h","9s"));
+        let dead = deck.new_hand().init(hand!("Kc"));
+        let r = ojp_equity(&[p1, p2], &board, &dead, Scale::HighHand,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.deals, 43);
+        assert_eq!(r.players[1].wins, 1);
+        assert_eq!(r.players[0].wins, 42);
+
+        let dead = deck.new_hand().init(hand!("Ah"));
+        let r = ojp_equity(&[p1, p2], &board, &dead, Scale::HighHand,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.players[1].wins, 2);
+        assert_eq!(r.players[0].wins, 41);
+        assert!(r.players.iter().all(|p| 0.0 == p.margin));
+
+        // Multiway flop: shares always total one
+        let board = deck.new_hand().init(hand!("Kc","7d","2s"));
+        let ex = ojp_equity(&[p1, p2, p3], &board, &empty, Scale::HighHand,
+            EquityMode::Exhaustive)?;
+        assert_eq!(ex.deals, 903);
+        let total: f64 = ex.players.iter().map(|p| p.share).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let mode = EquityMode::MonteCarlo { trials: 20000, seed: 12345 };
+        let mc = ojp_equity(&[p1, p2, p3], &board, &empty, Scale::HighHand, mode)?;
+        assert_eq!(mc, ojp_equity(&[p1, p2, p3], &board, &empty,
+            Scale::HighHand, mode)?);
+        assert_eq!(mc.deals, 20000);
+        for i in 0..3 {
+            let p = mc.players[i];
+            assert!(p.margin > 0.0 && p.margin < 0.01);
+            assert!((p.share - ex.players[i].share).abs() < 2.0 * p.margin);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_equity_games() -> Result<()> {
+        let deck = Deck::new(DeckType::English);
+        let empty = deck.new_hand();
+
+        // Omaha: a four-flush in hand plays only two of them
+        let p1 = deck.new_hand().init(hand!("Ah","Kh","Qh","Jh"));
+        let p2 = deck.new_hand().init(hand!("9s","9c","5d","4d"));
+        let board = deck.new_hand().init(hand!("Th","2h","9h","3c","8d"));
+        let r = ojp_equity(&[p1, p2], &board, &empty, Scale::HighHand,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.players[0].wins, 1);
+        let omaha = EquityGame::new(Scale::HighHand).usage(HoleUsage::OMAHA);
+        let r = ojp_equity(&[p1, p2], &board, &empty, omaha,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.players[0].wins, 1);
+
+        let p1 = deck.new_hand().init(hand!("Ah","Kc","Kd","5c"));
+        let r = ojp_equity(&[p1, p2], &board, &empty, omaha,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.players[1].wins, 1);
+
+        // Draw: one player drawing one card, other pat
+        let p1 = deck.new_hand().init(hand!("As","2d","3c","4h"));
+        let p2 = deck.new_hand().init(hand!("8s","6d","4c","3h","2s"));
+        let draw = EquityGame::new(Scale::AceToFive).board_size(0).hand_size(5);
+        let r = ojp_equity(&[p1, p2], &empty, &empty, draw,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.deals, 43);
+        assert_eq!(r.players[0].wins, 14);
+        assert!(ojp_equity_deals(&[p1, p2], &empty, &empty, draw)?.is_some());
+
+        // Errors
+        let p2 = deck.new_hand().init(hand!("As","Kd"));
+        assert!(ojp_equity(&[p1, p2], &empty, &empty, draw,
+            EquityMode::Exhaustive).is_err());
+        assert!(ojp_equity(&[p1], &empty, &empty, draw,
+            EquityMode::Exhaustive).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_equity() -> Result<()> {
+        let deck = Deck::new(DeckType::English);
+        let empty = deck.new_hand();
+        let g = Scale::HighHand;
+
+        // Single hands match plain equity
+        let p1 = deck.new_hand().init(hand!("As","Ad"));
+        let p2 = deck.new_hand().init(hand!("Ks","Kd"));
+        let board = deck.new_hand().init(hand!("Kc","7d","2s"));
+        let ex = ojp_equity(&[p1, p2], &board, &empty, g, EquityMode::Exhaustive)?;
+        let r = ojp_range_equity(&[Range::from(&p1), Range::from(&p2)], &board,
+            &empty, g, EquityMode::Exhaustive)?;
+        assert_eq!(r.deals, ex.deals);
+        assert_eq!(r.players, ex.players);
+        assert_eq!(r.combos[0][0].frequency, 1.0);
+
+        // Kings lose one combo to the board; per-hand results add up
+        let aa = Range::parse("AA")?;
+        let kk = Range::parse("KK")?;
+        let r = ojp_range_equity(&[aa.clone(), kk.clone()], &board, &empty, g,
+            EquityMode::Exhaustive)?;
+        assert_eq!(r.deals, 6 * 3 * 990);
+        assert_eq!(r.combos[0].len(), 6);
+        assert_eq!(r.combos[1].len(), 3);
+        for (i, p) in r.players.iter().enumerate() {
+            let f: f64 = r.combos[i].iter().map(|c| c.frequency).sum();
+            let s: f64 = r.combos[i].iter().map(|c| c.frequency * c.share).sum();
+            assert!((f - 1.0).abs() < 1e-9);
+            assert!((s - p.share).abs() < 1e-9);
+        }
+        assert!((r.combos[0][0].frequency - 1.0 / 6.0).abs() < 1e-9);
+
+        // Card removal: each AK shares the one ace the pair leaves
+        let board = deck.new_hand().init(hand!("Ah","7d","2s","3c"));
+        let r = ojp_range_equity(&[Range::parse("AK")?, aa.clone()], &board,
+            &empty, g, EquityMode::Exhaustive)?;
+        assert_eq!(r.deals, 12 * 44);
+        assert_eq!(Some(r.deals), ojp_range_equity_deals(&[Range::parse("AK")?,
+            aa.clone()], &board, &empty, g)?);
+        assert!(r.combos[0].iter().all(|c|
+            (c.frequency - 1.0 / 12.0).abs() < 1e-9));
+
+        // Weights
+        let board = deck.new_hand().init(hand!("2c","3d","7h","9s"));
+        let qq = Range::from(&deck.new_hand().init(hand!("Qs","Qd")));
+        let r = ojp_range_equity(&[Range::parse("AA:0.5, KK")?, qq.clone()],
+            &board, &empty, g, EquityMode::Exhaustive)?;
+        let f: f64 = r.combos[0].iter().filter(|c| c.hand[0].rank() == Rank::Ace)
+            .map(|c| c.frequency).sum();
+        assert!((f - 1.0 / 3.0).abs() < 1e-9);
+        assert!(r.players[1].share < 0.1);
+
+        // Monte Carlo, preflop
+        let mode = EquityMode::MonteCarlo { trials: 5000, seed: 54321 };
+        let mc = ojp_range_equity(&[aa.clone(), kk.clone()], &empty, &empty,
+            g, mode)?;
+        assert_eq!(mc, ojp_range_equity(&[aa.clone(), kk.clone()], &empty,
+            &empty, g, mode)?);
+        assert_eq!(mc.deals, 5000);
+        assert!(! mc.exhaustive);
+        let p = mc.players[0];
+        assert!((p.share - 0.82).abs() < 2.0 * p.margin);
+        assert_eq!(ojp_range_equity_deals(&[aa.clone(), kk.clone()], &empty,
+            &empty, g)?, Some(36 * 1712304));
+
+        // Errors
+        let dead = deck.new_hand().init(hand!("As","Ad","Ah"));
+        assert!(ojp_range_equity(&[aa.clone(), kk.clone()], &empty, &dead, g,
+            EquityMode::Exhaustive).is_err());
+        let r1 = Range::from(&deck.new_hand().init(hand!("As","Ad")));
+        let r2 = Range::from(&deck.new_hand().init(hand!("As","Kd")));
+        assert!(ojp_range_equity(&[r1.clone(), r2], &empty, &empty, g,
+            mode).is_err());
+        assert!(ojp_range_equity(&[r1], &empty, &empty, g, mode).is_err());
+
+        // Dead cards and combos in the game's own deck
+        for g in [Scale::ShortDeck, Scale::AceToFive] {
+            let dead = Hand::new(g.deck_type()).init(hand!("As","Ad","Kc"));
+            let r = ojp_range_equity(&[aa.clone(), kk.clone()], &empty, &dead,
+                g, mode)?;
+            assert_eq!(r.combos[0].len(), 1);
+            assert_eq!(r.combos[1].len(), 3);
+        }
+        assert!(ojp_range_equity(&[aa.clone(), Range::parse("22")?], &empty,
+            &empty, Scale::ShortDeck, mode).is_err());
+        Ok(())
+    }
+}
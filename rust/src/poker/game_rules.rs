@@ -0,0 +1,577 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Game_Rules) | Rules of whole poker games
+//!
+//! A [Scale] only says how hands rank; [GameRules] adds the deck, how the
+//! cards are dealt and drawn, how hole cards combine with the board, and
+//! how the pot is split, so that dealing, equity, and game engine code can
+//! all be set up from a game name. The built-in games are also listed in
+//! `data/json/master_games.jsonc`, and more can be loaded from files in
+//! the same format.
+
+use std::sync::OnceLock;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::error::{Error, Result};
+use crate::cards::*;
+use crate::poker::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Street) | One round of dealing or drawing
+///
+/// Cards dealt to each player and the board in one round, followed by a
+/// round of betting. In draw games, the players first replace up to
+/// `draw` cards.
+/// ```rust
+/// use onejoker::poker::Street;
+///
+/// let third = Street::new().down(2).up(1);
+/// assert_eq!(third.cards_per_player(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct Street {
+    /// Cards dealt face down to each player
+    pub down: usize,
+    /// Cards dealt face up to each player
+    pub up: usize,
+    /// Community cards dealt to the board
+    pub board: usize,
+    /// Most cards each player may replace, or 0 for no draw
+    pub draw: usize,
+}
+
+impl Street {
+    /// Street with no cards dealt
+    pub const fn new() -> Self {
+        Street { down: 0, up: 0, board: 0, draw: 0 }
+    }
+
+    /// Set cards dealt face down to each player
+    pub const fn down(mut self, n: usize) -> Self {
+        self.down = n;
+        self
+    }
+
+    /// Set cards dealt face up to each player
+    pub const fn up(mut self, n: usize) -> Self {
+        self.up = n;
+        self
+    }
+
+    /// Set cards dealt to the board
+    pub const fn board(mut self, n: usize) -> Self {
+        self.board = n;
+        self
+    }
+
+    /// Set most cards each player may replace
+    pub const fn draw(mut self, n: usize) -> Self {
+        self.draw = n;
+        self
+    }
+
+    /// Cards dealt to each player
+    pub const fn cards_per_player(&self) -> usize {
+        self.down + self.up
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/GameRules) | Rules of a poker game
+///
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{GameRules, HoleUsage};
+///
+/// let o8 = GameRules::by_name("omaha-8").unwrap();
+/// assert_eq!(o8.hole_cards(), 4);
+/// assert_eq!(o8.board_cards(), 5);
+/// assert_eq!(o8.usage, HoleUsage::OMAHA);
+/// assert_eq!(o8.split_rules().unwrap().qualifier, Some(Rank::Eight));
+///
+/// let razz = GameRules::by_name("razz").unwrap();
+/// assert_eq!(razz.scale, Scale::AceToFive);
+/// assert_eq!(razz.streets.len(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameRules {
+    /// Canonical name, e.g. "holdem"
+    pub name: String,
+    /// Other names for the game
+    pub aliases: Vec<String>,
+    /// Deck the game is dealt from
+    pub deck: DeckType,
+    /// Scale for the whole pot, or the high half of a split pot
+    pub scale: Scale,
+    /// Scale for the low half of a split pot, if any
+    pub low: Option<Scale>,
+    /// Highest card allowed in a qualifying low, or `None` for any low
+    pub qualifier: Option<Rank>,
+    /// How many hole cards must play
+    pub usage: HoleUsage,
+    /// Rounds of dealing and drawing, in order
+    pub streets: Vec<Street>,
+}
+
+impl GameRules {
+    /// New game using the scale's preferred deck, any hole cards, and no
+    /// streets yet
+    pub fn new(name: &str, scale: Scale) -> Self {
+        GameRules {
+            name: name.to_string(),
+            aliases: Vec::new(),
+            deck: scale.deck_type(),
+            scale,
+            low: None,
+            qualifier: None,
+            usage: HoleUsage::Any,
+            streets: Vec::new(),
+        }
+    }
+
+    /// Add other names for the game
+    pub fn aliases(mut self, names: &[&str]) -> Self {
+        self.aliases.extend(names.iter().map(|s| s.to_string()));
+        self
+    }
+
+    /// Set deck the game is dealt from
+    pub fn deck(mut self, t: DeckType) -> Self {
+        self.deck = t;
+        self
+    }
+
+    /// Split the pot with a low half of the given scale and qualifier
+    pub fn low(mut self, low: Scale, qualifier: Option<Rank>) -> Self {
+        self.low = Some(low);
+        self.qualifier = qualifier;
+        self
+    }
+
+    /// Set rule for how many hole cards must play
+    pub fn usage(mut self, rule: HoleUsage) -> Self {
+        self.usage = rule;
+        self
+    }
+
+    /// Add rounds of dealing or drawing
+    pub fn streets(mut self, streets: &[Street]) -> Self {
+        self.streets.extend_from_slice(streets);
+        self
+    }
+
+    /// Built-in game by name or alias
+    pub fn by_name(name: &str) -> Option<&'static GameRules> {
+        GameRegistry::builtin().get(name)
+    }
+
+    /// Does the name or one of the aliases match (ignoring case)?
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) ||
+            self.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    }
+
+    /// Total cards dealt to each player
+    pub fn hole_cards(&self) -> usize {
+        self.streets.iter().map(Street::cards_per_player).sum()
+    }
+
+    /// Total cards dealt to the board
+    pub fn board_cards(&self) -> usize {
+        self.streets.iter().map(|s| s.board).sum()
+    }
+
+    /// Number of drawing rounds
+    pub fn draws(&self) -> usize {
+        self.streets.iter().filter(|s| s.draw > 0).count()
+    }
+
+    /// Is the pot split between high and low hands?
+    pub fn is_split(&self) -> bool {
+        self.low.is_some()
+    }
+
+    /// Rules for splitting the pot, if it is split
+    pub fn split_rules(&self) -> Option<SplitRules> {
+        self.low.map(|low| SplitRules::new(self.scale, self.usage)
+            .low(low).qualifier(self.qualifier))
+    }
+
+    /// Shape of the final deal for equity calculations, using the high
+    /// scale of split games
+    pub fn equity_game(&self) -> EquityGame {
+        EquityGame::new(self.scale).usage(self.usage)
+            .board_size(self.board_cards())
+            .hand_size(self.hole_cards())
+    }
+
+    /// New unshuffled deck for the game
+    pub fn new_deck(&self) -> Deck {
+        Deck::new(self.deck)
+    }
+
+    /// Check that the rules make sense
+    pub fn validate(&self) -> Result<()> {
+        let bad = |msg: &str| Err(Error::BadGame(format!("{}: {}", self.name, msg)));
+
+        if self.name.is_empty() {
+            return Err(Error::BadGame("game has no name".into()));
+        }
+        if Scale::None == self.scale || Some(Scale::None) == self.low {
+            return bad("no scale");
+        }
+        if self.qualifier.is_some() && self.low.is_none() {
+            return bad("qualifier without low scale");
+        }
+        if self.streets.is_empty() || 0 == self.hole_cards() {
+            return bad("no cards dealt");
+        }
+        if self.hole_cards() > Hand::default().capacity() {
+            return bad("too many hole cards");
+        }
+        let per_player = self.hole_cards() + self.board_cards();
+        if per_player > self.deck.size() {
+            return bad("not enough cards in deck");
+        }
+        if let HoleUsage::Exactly(n) | HoleUsage::AtLeast(n) = self.usage {
+            if n > self.hole_cards() {
+                return bad("more hole cards must play than are dealt");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&GameRules> for EquityGame {
+    fn from(g: &GameRules) -> Self {
+        g.equity_game()
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/GameRegistry) | Collection of games by name
+///
+/// The built-in registry has the common poker variants; others can be
+/// built in code or read from a JSON5 file like
+/// `data/json/master_games.jsonc`, where each game looks like this:
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::GameRegistry;
+///
+/// let text = r#"[{
+///     "name": "big-o",
+///     "aliases": ["five-card-omaha-8"],
+///     "deck": "english",
+///     "scale": "high-hand",
+///     "low": "ace-to-five",
+///     "qualifier": "8",
+///     "usage": "exactly 2",
+///     "streets": [ {"down": 5}, {"board": 3}, {"board": 1}, {"board": 1} ],
+/// }]"#;
+/// let r: GameRegistry = text.parse().unwrap();
+/// assert_eq!(r.get("Five-Card-Omaha-8").unwrap().hole_cards(), 5);
+/// ```
+/// Only `name`, `scale`, and `streets` are required; the deck defaults to
+/// the scale's deck and the usage to "any".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameRegistry {
+    games: Vec<GameRules>,
+}
+
+impl GameRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        GameRegistry::default()
+    }
+
+    /// Registry of built-in games
+    pub fn builtin() -> &'static GameRegistry {
+        static BUILTIN: OnceLock<GameRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(|| GameRegistry { games: builtin_games() })
+    }
+
+    /// Game by name or alias, ignoring case
+    pub fn get(&self, name: &str) -> Option<&GameRules> {
+        self.games.iter().find(|g| g.is_named(name))
+    }
+
+    /// Add game after checking it, replacing any with the same name
+    pub fn set(&mut self, rules: GameRules) -> Result<()> {
+        rules.validate()?;
+        match self.games.iter_mut().find(|g| g.name == rules.name) {
+            Some(g) => *g = rules,
+            None => self.games.push(rules),
+        }
+        Ok(())
+    }
+
+    /// Iterate over games in order added
+    pub fn iter(&self) -> impl Iterator<Item = &GameRules> {
+        self.games.iter()
+    }
+
+    /// Number of games
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Are there no games?
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// Read games from JSON5 file
+    #[cfg(feature = "serde")]
+    pub fn read<P: AsRef<std::path::Path>>(path: P) -> Result<GameRegistry> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::IO(format!("{}: {}", path.display(), e)))?;
+        text.parse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::str::FromStr for GameRegistry {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<GameRegistry> {
+        let data: Vec<GameRulesData> = serde_json5::from_str(text)?;
+        let mut r = GameRegistry::new();
+
+        for d in data {
+            r.set(d.into_rules()?)?;
+        }
+        Ok(r)
+    }
+}
+
+// Game as written in JSON, with scales and decks by name
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameRulesData {
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    deck: Option<String>,
+    scale: String,
+    low: Option<String>,
+    qualifier: Option<String>,
+    usage: Option<String>,
+    streets: Vec<Street>,
+}
+
+#[cfg(feature = "serde")]
+impl GameRulesData {
+    fn into_rules(self) -> Result<GameRules> {
+        let scale = scale_named(&self.scale)?;
+        let mut g = GameRules::new(&self.name, scale);
+        g.aliases = self.aliases;
+
+        if let Some(d) = self.deck {
+            g.deck = (1..=u8::MAX).map(DeckType::from_u8)
+                .take_while(|&t| DeckType::None != t)
+                .find(|t| t.name() == d)
+                .ok_or_else(|| Error::BadGame(format!("{}: unknown deck {}",
+                    self.name, d)))?;
+        }
+        if let Some(l) = self.low {
+            g.low = Some(scale_named(&l)?);
+        }
+        if let Some(q) = self.qualifier {
+            let mut chars = q.chars();
+            let r = match (chars.next(), chars.next()) {
+                (Some(c), None) => Rank::from_char(c),
+                _ => Rank::None,
+            };
+            if Rank::None == r {
+                return Err(Error::BadGame(format!("{}: bad qualifier {}",
+                    self.name, q)));
+            }
+            g.qualifier = Some(r);
+        }
+        if let Some(u) = self.usage {
+            g.usage = u.parse()?;
+        }
+        g.streets = self.streets;
+        Ok(g)
+    }
+}
+
+// Scale by canonical name only, since unknown aliases fall back to high
+#[cfg(feature = "serde")]
+fn scale_named(name: &str) -> Result<Scale> {
+    (1..=u8::MAX).map(Scale::from_u8)
+        .take_while(|&g| Scale::None != g)
+        .find(|g| g.name() == name)
+        .ok_or_else(|| Error::BadGame(format!("unknown scale {}", name)))
+}
+
+const HOLDEM: [Street; 4] = [
+    Street::new().down(2), Street::new().board(3),
+    Street::new().board(1), Street::new().board(1),
+];
+const OMAHA: [Street; 4] = [
+    Street::new().down(4), Street::new().board(3),
+    Street::new().board(1), Street::new().board(1),
+];
+const COURCHEVEL: [Street; 4] = [
+    Street::new().down(5).board(1), Street::new().board(2),
+    Street::new().board(1), Street::new().board(1),
+];
+const SEVEN_STUD: [Street; 5] = [
+    Street::new().down(2).up(1), Street::new().up(1), Street::new().up(1),
+    Street::new().up(1), Street::new().down(1),
+];
+const FIVE_STUD: [Street; 4] = [
+    Street::new().down(1).up(1), Street::new().up(1), Street::new().up(1),
+    Street::new().up(1),
+];
+const SINGLE_DRAW: [Street; 2] = [
+    Street::new().down(5), Street::new().draw(5),
+];
+const TRIPLE_DRAW: [Street; 4] = [
+    Street::new().down(5), Street::new().draw(5),
+    Street::new().draw(5), Street::new().draw(5),
+];
+const BADUGI: [Street; 4] = [
+    Street::new().down(4), Street::new().draw(4),
+    Street::new().draw(4), Street::new().draw(4),
+];
+
+// Keep in sync with data/json/master_games.jsonc
+fn builtin_games() -> Vec<GameRules> {
+    use Scale::*;
+    let eight = Some(Rank::Eight);
+
+    vec![
+        GameRules::new("holdem", HighHand)
+            .aliases(&["texas-holdem", "hold-em"])
+            .streets(&HOLDEM),
+        GameRules::new("omaha", HighHand)
+            .aliases(&["omaha-high", "plo"])
+            .usage(HoleUsage::OMAHA).streets(&OMAHA),
+        GameRules::new("omaha-8", HighHand)
+            .aliases(&["omaha-hi-lo", "o8"])
+            .low(AceToFive, eight).usage(HoleUsage::OMAHA).streets(&OMAHA),
+        GameRules::new("courchevel", HighHand)
+            .usage(HoleUsage::COURCHEVEL).streets(&COURCHEVEL),
+        GameRules::new("short-deck", ShortDeck)
+            .aliases(&["six-plus-holdem", "6+"])
+            .streets(&HOLDEM),
+        GameRules::new("seven-card-stud", HighHand)
+            .aliases(&["stud", "7-stud"])
+            .streets(&SEVEN_STUD),
+        GameRules::new("stud-8", HighHand)
+            .aliases(&["stud-hi-lo", "seven-card-stud-8"])
+            .low(AceToFive, eight).streets(&SEVEN_STUD),
+        GameRules::new("razz", AceToFive)
+            .streets(&SEVEN_STUD),
+        GameRules::new("five-card-stud", HighHand)
+            .aliases(&["5-stud"])
+            .streets(&FIVE_STUD),
+        GameRules::new("five-card-draw", HighHand)
+            .aliases(&["draw", "5-draw"])
+            .streets(&SINGLE_DRAW),
+        GameRules::new("deuce-to-seven-single-draw", DeuceToSeven)
+            .aliases(&["2-7-single-draw", "27sd"])
+            .streets(&SINGLE_DRAW),
+        GameRules::new("deuce-to-seven-triple-draw", DeuceToSeven)
+            .aliases(&["2-7-triple-draw", "27td"])
+            .streets(&TRIPLE_DRAW),
+        GameRules::new("ace-to-five-triple-draw", AceToFive)
+            .aliases(&["a-5-triple-draw"])
+            .streets(&TRIPLE_DRAW),
+        GameRules::new("california-lowball", AceToFiveBug)
+            .aliases(&["lowball"])
+            .streets(&SINGLE_DRAW),
+        GameRules::new("badugi", Badugi)
+            .streets(&BADUGI),
+    ]
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_rules() -> Result<()> {
+        let r = GameRegistry::builtin();
+        assert_eq!(r.len(), 15);
+
+        for g in r.iter() {
+            g.validate()?;
+            assert_eq!(r.get(&g.name.to_uppercase()), Some(g));
+            for a in g.aliases.iter() {
+                assert_eq!(GameRules::by_name(a), Some(g));
+            }
+            let mut deck = g.new_deck();
+            assert!(deck.size() >= g.hole_cards() * 2 + g.board_cards());
+            let h = deck.new_hand().init(deck.draw(g.hole_cards()));
+            assert_eq!(h.len(), g.hole_cards());
+        }
+        assert!(GameRules::by_name("pinochle").is_none());
+
+        let holdem = GameRules::by_name("hold-em").unwrap();
+        assert_eq!((holdem.hole_cards(), holdem.board_cards()), (2, 5));
+        assert!(! holdem.is_split());
+        assert_eq!(EquityGame::from(holdem),
+            EquityGame::new(Scale::HighHand).board_size(5).hand_size(2));
+
+        let stud8 = GameRules::by_name("stud-8").unwrap();
+        assert_eq!(stud8.split_rules(), Some(SplitRules::STUD_8));
+        assert_eq!(stud8.hole_cards(), 7);
+        assert_eq!(stud8.board_cards(), 0);
+
+        let td = GameRules::by_name("27td").unwrap();
+        assert_eq!(td.draws(), 3);
+        assert_eq!(td.deck, DeckType::English);
+
+        let mut mine = GameRegistry::new();
+        assert!(mine.is_empty());
+        assert!(matches!(mine.set(GameRules::new("nothing", Scale::HighHand)),
+            Err(Error::BadGame(_))));
+        assert!(mine.set(GameRules::new("too-many", Scale::HighHand)
+            .usage(HoleUsage::Exactly(3)).streets(&HOLDEM)).is_err());
+        assert!(mine.set(GameRules::new("no-low", Scale::HighHand)
+            .streets(&HOLDEM).low(Scale::None, None)).is_err());
+        mine.set(GameRules::new("pineapple", Scale::HighHand)
+            .streets(&[Street::new().down(3)]))?;
+        mine.set(GameRules::new("pineapple", Scale::HighHand)
+            .streets(&HOLDEM))?;
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine.get("pineapple").unwrap().hole_cards(), 2);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_game_rules_json() -> Result<()> {
+        let r: GameRegistry = r#"[
+            { "name": "a", "scale": "ace-to-six", "streets": [ {"down": 5} ] },
+            { "name": "b", "scale": "badugi", "deck": "lowjoker",
+              "usage": "at most 2", "streets": [ {"down": 4, "draw": 0} ] },
+        ]"#.parse()?;
+        assert_eq!(r.get("a").unwrap().deck, DeckType::Low);
+        assert_eq!(r.get("b").unwrap().deck, DeckType::LowJoker);
+        assert_eq!(r.get("b").unwrap().usage, HoleUsage::AtMost(2));
+
+        for bad in [
+            r#"[{ "name": "a", "scale": "hi", "streets": [{"down": 5}] }]"#,
+            r#"[{ "name": "a", "scale": "badugi", "deck": "nope",
+                "streets": [{"down": 5}] }]"#,
+            r#"[{ "name": "a", "scale": "badugi", "streets": [{"down": 5}],
+                "low": "ace-to-five", "qualifier": "88" }]"#,
+            r#"[{ "name": "a", "scale": "badugi", "streets": [{"dwon": 5}] }]"#,
+            r#"[{ "name": "a", "scale": "badugi", "streets": [] }]"#,
+            r#"[{ "name": "a", "scale": "badugi", "streets": [{"down": 4}],
+                "usage": "some" }]"#,
+        ] {
+            assert!(bad.parse::<GameRegistry>().is_err(), "{}", bad);
+        }
+        Ok(())
+    }
+}
@@ -52,6 +52,14 @@ pub fn ojp_a5_eval_5(h: &Hand) -> HandValue {
     ojp_reference_evaluator(h, Scale::AceToFive)
 }
 
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ace_to_five_eval_7) | Ace-to-five 7-card evaluator
+///
+/// Works for any hand of 5 or more cards without lookup tables.
+#[cfg(not(feature = "ace-to-five-tables"))]
+pub fn ojp_a5_eval_7(h: &Hand) -> HandValue {
+    ojp_lowball_evaluator(h, Scale::AceToFive)
+}
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ace_to_five_eval_5) | Ace-to-five 5-card evaluator
 #[cfg(feature = "ace-to-five-tables")]
 pub fn  ojp_a5_eval_5(h: &Hand) -> HandValue {
@@ -91,7 +99,7 @@ pub fn ojp_a5_value(h: &Hand) -> HandValue {
     match h.len() {
         ..5 => ojp_reference_evaluator(h, Scale::AceToFive),
         5 => ojp_a5_eval_5(h),
-        6.. => ojp_best_of(h, 5, Scale::AceToFive, ojp_a5_eval_5),
+        6.. => ojp_a5_eval_7(h),
     }
 }
 
@@ -121,14 +129,25 @@ pub fn ojp_cl_full_text(d: &HandDescription) -> String {
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_cl_description) | Describe ace-to-five hand with bug
 #[cfg(not(feature = "ace-to-five-tables"))]
 pub fn ojp_cl_description(h: &Hand, v: HandValue) -> HandDescription {
-    HandDescription::from_value(h, Scale::AceToFiveBug, v)
+    cl_description(h, v)
 }
 
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_cl_description) | Describe ace-to-five hand with bug
 #[cfg(feature = "ace-to-five-tables")]
 pub fn ojp_cl_description(h: &Hand, v: HandValue) -> HandDescription {
-    HandDescription::from_value(h, Scale::AceToFiveBug,
-        OJP_A5_TABLE_2[v as usize])
+    cl_description(h, OJP_A5_TABLE_2[v as usize])
+}
+
+// Describe the hand with the bug replaced, and record the replacement
+fn cl_description(h: &Hand, v: HandValue) -> HandDescription {
+    let Some(sr) = ojp_bug_scan_low_1(h) else {
+        return HandDescription::from_value(h, Scale::AceToFiveBug, v);
+    };
+    let mut bh = *h;
+    bh[sr.index as usize] = sr.replacement;
+    let mut d = HandDescription::from_value(&bh, Scale::AceToFiveBug, v);
+    d.add_bugs([sr.replacement, Card::default()]);
+    d
 }
 
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_cl_eval_5) | Ace-to-five with bug 5-card evaluator
@@ -140,6 +159,23 @@ pub fn ojp_cl_eval_5(h: &Hand) -> HandValue {
     bh[sr.index as usize] = sr.replacement;
     ojp_a5_eval_5(&bh)
 }
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_cl_eval_7) | Ace-to-five with bug 7-card evaluator
+///
+/// For a low hand the bug is always best used as the lowest rank missing
+/// from all seven cards, so we can replace it once up front rather than
+/// scanning each 5-card subset (California razz).
+pub fn ojp_cl_eval_7(h: &Hand) -> HandValue {
+    debug_assert!(7 == h.len());
+
+    let Some(sr) = ojp_bug_scan_low_1(h) else {
+        return ojp_a5_value(h)
+    };
+    let mut bh = *h;
+    bh[sr.index as usize] = sr.replacement;
+    ojp_a5_value(&bh)
+}
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_cl_value) | Ace-to-five with bug evaluator
 /// ```rust
 /// use onejoker::prelude::*;
@@ -163,7 +199,9 @@ pub fn ojp_cl_value(h: &Hand) -> HandValue {
             }
         },
         5 => ojp_cl_eval_5(h),
-        6.. => ojp_best_of(h, 5, Scale::AceToFiveBug, ojp_cl_eval_5),
+        6 => ojp_best_of(h, 5, Scale::AceToFiveBug, ojp_cl_eval_5),
+        7 => ojp_cl_eval_7(h),
+        8.. => ojp_best_of(h, 7, Scale::AceToFiveBug, ojp_cl_eval_7),
     }
 }
 
@@ -380,9 +418,51 @@ mod tests {
 
         hand.set(hand!("Jk","2c","Ah","3s","4d"));
         v2 = ojp_cl_value(&hand);
+        d1 = ojp_cl_description(&hand, v2);
         assert_eq!(v1, v2);
         assert!(v1 < best);
+        assert_eq!(d1.hand[0], FIVE_OF_SPADES);
 
+        match d1.extras {
+            HandExtras::Bugs(b) => assert_eq!(b[0], FIVE_OF_SPADES),
+            _ => panic!("bug not recorded"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_hand_evaluator_cl_7() -> Result<()> {
+        let deck = Deck::new_by_name("lowjoker");
+        let mut hand= deck.new_hand();
+
+        hand.set(hand!("Kd","2c","Jk","4s","4d","Ah","3s"));
+        let v1 = ojp_cl_value(&hand);
+        let d1 = ojp_cl_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert_eq!(d1.hand[0], FIVE_OF_SPADES);
+        assert_eq!(d1.hand[4].rank(), Rank::LowAce);
+
+        hand.set(hand!("Kd","2c","5h","4s","4d","Ah","3s"));
+        let v2 = ojp_cl_value(&hand);
+        assert_eq!(v1, v2);
+
+        // Partial hands get the lowest missing rank as well
+        hand.set(hand!("Ac","Jk","9d"));
+        let v3 = ojp_cl_value(&hand);
+        let d3 = ojp_cl_description(&hand, v3);
+        assert_eq!(d3.level, HandLevel::NoPair);
+        assert_eq!(d3.hand[1], DEUCE_OF_SPADES);
+
+        // Compare with best-of on random hands
+        let mut deck = Deck::new_by_name("low");
+        for _ in 0..200 {
+            deck.refill_and_shuffle();
+            let mut h = hand;
+            h.set(deck.draw(6));
+            h.push(JOKER);
+            assert_eq!(ojp_cl_eval_7(&h),
+                ojp_best_of(&h, 5, Scale::AceToFiveBug, ojp_cl_eval_5));
+        }
         Ok(())
     }
 
@@ -32,6 +32,20 @@ pub fn ojp_ll_eval_5(h: &Hand) -> HandValue {
     OJP_LL_TABLE_1[h as usize] as u32
 }
 
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ll_eval_7) | Ace-to-six 7-card evaluator
+///
+/// Works for any hand of 5 or more cards without lookup tables.
+#[cfg(not(feature = "deuce-to-seven-tables"))]
+pub fn ojp_ll_eval_7(h: &Hand) -> HandValue {
+    ojp_lowball_evaluator(h, Scale::AceToSix)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ll_eval_7) | Ace-to-six 7-card evaluator
+#[cfg(feature = "deuce-to-seven-tables")]
+pub fn ojp_ll_eval_7(h: &Hand) -> HandValue {
+    ojp_best_of(h, 5, Scale::AceToSix, ojp_ll_eval_5)
+}
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ace_to_six_value) | Ace-to-six evaluator
 /// ```rust
 /// use onejoker::prelude::*;
@@ -49,7 +63,7 @@ pub fn ojp_ll_value(h: &Hand) -> HandValue {
     match h.len() {
         ..5 => ojp_reference_evaluator(h, Scale::AceToSix),
         5 => ojp_ll_eval_5(h),
-        6.. => ojp_best_of(h, 5, Scale::AceToSix, ojp_ll_eval_5),
+        6.. => ojp_ll_eval_7(h),
     }
 }
 
@@ -0,0 +1,226 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/ActionRazz) | Action razz hand values
+
+use crate::cards::*;
+use crate::poker::*;
+
+#[cfg(feature = "ace-to-five-tables")]
+use crate::poker::tables::ace_to_five_tables::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ar_full_text) | Describe action razz hand
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::games::action_razz::*;
+///
+/// let hand = Hand::new(DeckType::Low).init(hand!("7s","4s","As","5d","2c"));
+/// let v = ojp_ar_value(&hand);
+/// let d = ojp_ar_description(&hand, v);
+/// println!("{}", ojp_ar_full_text(&d));
+/// // Output: "unqualified seven-five"
+/// ```
+pub fn ojp_ar_full_text(d: &HandDescription) -> String {
+    macro_rules! sng {
+        ($x:literal) => { d.hand[$x as usize].rank().name() }
+    }
+    let ql = qualified_level(d.level);
+    if ql == d.level {
+        return ojp_a5_full_text(d);
+    }
+    if HandLevel::NoPair == ql {
+        return format!("unqualified {}-{}", sng!(0), sng!(1));
+    }
+    let mut qd = *d;
+    qd.level = ql;
+    format!("unqualified {}", ojp_a5_full_text(&qd))
+}
+
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_ar_description) | Action razz hand description
+pub fn ojp_ar_description(h: &Hand, v: HandValue) -> HandDescription {
+    HandDescription::from_value(h, Scale::ActionRazz, v)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ar_value) | Action razz hand evaluator
+///
+/// Ace-to-five low, but a hand with no face card ranks below every hand
+/// that has one.
+/// ```rust
+/// use onejoker::prelude::*;
+///
+/// let h1 = Hand::new(DeckType::Low).init(hand!("Ks","Kh","Kd","Kc","Qs"));
+/// let h2 = Hand::new(DeckType::Low).init(hand!("5s","4h","3d","2c","As"));
+/// let v1 = Scale::ActionRazz.value(&h1);
+/// let v2 = Scale::ActionRazz.value(&h2);
+/// assert!(v1 < v2);   // any qualified hand beats an unqualified wheel
+/// ```
+pub fn ojp_ar_value(h: &Hand) -> HandValue {
+    debug_assert!(Scale::ActionRazz.valid_hand(h));
+
+    match h.len() {
+        ..6 => ojp_ar_eval_5(h),
+        6.. => ojp_best_of(h, 5, Scale::ActionRazz, ojp_ar_eval_5),
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_ar_eval_5) | 5-card action razz evaluator
+///
+/// The face card rule applies to the cards given, so larger hands must
+/// go through [ojp_best_of] rather than here.
+pub fn ojp_ar_eval_5(h: &Hand) -> HandValue {
+    let v = a5_full_value(h);
+    if has_face_card(h) {
+        return v;
+    }
+    let l = Scale::AceToFive.level_from_value(v / HAND_LEVEL_MULTIPLIER);
+    HAND_LEVEL_MULTIPLIER * Scale::ActionRazz.value_from_level(unqualified_level(l))
+    + v % HAND_LEVEL_MULTIPLIER
+}
+
+// Ace-to-five value, expanded from equivalence class if using tables
+#[cfg(not(feature = "ace-to-five-tables"))]
+fn a5_full_value(h: &Hand) -> HandValue {
+    ojp_a5_value(h)
+}
+
+#[cfg(feature = "ace-to-five-tables")]
+fn a5_full_value(h: &Hand) -> HandValue {
+    OJP_A5_TABLE_2[ojp_a5_value(h) as usize]
+}
+
+fn has_face_card(h: &Hand) -> bool {
+    h[..].iter().any(|c| c.rank() >= Rank::Jack && c.rank() <= Rank::King)
+}
+
+fn unqualified_level(l: HandLevel) -> HandLevel {
+    match l {
+        HandLevel::FiveOfAKind => HandLevel::UnqualifiedFiveOfAKind,
+        HandLevel::Quads => HandLevel::UnqualifiedQuads,
+        HandLevel::FullHouse => HandLevel::UnqualifiedFullHouse,
+        HandLevel::Trips => HandLevel::UnqualifiedTrips,
+        HandLevel::TwoPair => HandLevel::UnqualifiedTwoPair,
+        HandLevel::Pair => HandLevel::UnqualifiedPair,
+        HandLevel::NoPair => HandLevel::UnqualifiedNoPair,
+        _ => l,
+    }
+}
+
+fn qualified_level(l: HandLevel) -> HandLevel {
+    match l {
+        HandLevel::UnqualifiedFiveOfAKind => HandLevel::FiveOfAKind,
+        HandLevel::UnqualifiedQuads => HandLevel::Quads,
+        HandLevel::UnqualifiedFullHouse => HandLevel::FullHouse,
+        HandLevel::UnqualifiedTrips => HandLevel::Trips,
+        HandLevel::UnqualifiedTwoPair => HandLevel::TwoPair,
+        HandLevel::UnqualifiedPair => HandLevel::Pair,
+        HandLevel::UnqualifiedNoPair => HandLevel::NoPair,
+        _ => l,
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_hand_evaluator_ar() -> Result<()> {
+        let deck = Deck::new_by_name("low");
+        let mut hand= deck.new_hand();
+        let mut best: u32 = HAND_VALUE_WORST;
+
+        hand.set(hand!("Ts","Th","Td","Tc","9s"));
+        let mut v1 = ojp_ar_value(&hand);
+        let mut d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::UnqualifiedQuads);
+        assert_eq!(d1.full_text(), "unqualified four tens with a nine");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("8d","5c","8c","2h","5s"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::UnqualifiedTwoPair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Tc","9d","6h","3s","Ac"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::UnqualifiedNoPair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("7s","4s","As","5d","2c"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::UnqualifiedNoPair);
+        assert_eq!(d1.full_text(), "unqualified seven-five");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("5s","4h","3d","2c","As"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::UnqualifiedNoPair);
+        assert_eq!(d1.full_text(), "unqualified five-four");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ks","Kh","Kd","Kc","Qs"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Quads);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Jd","5c","Jc","Jh","5s"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::FullHouse);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Qd","9c","Qh","4h","8s"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Pair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ks","Qs","8d","5c","3h"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert_eq!(d1.full_text(), "king, queen, eight, five, trey");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Jh","4s","3s","2d","Ad"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert!(v1 < best);
+        best = v1;
+
+        // Face card must be one of the five cards that play
+        hand.set(hand!("Ks","7d","5c","4h","2s","As","3d"));
+        v1 = ojp_ar_value(&hand);
+        d1 = ojp_ar_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert_eq!(d1.full_text(), "king, four, trey, deuce, ace");
+        assert_eq!(v1, ojp_ar_value(&deck.new_hand()
+            .init(hand!("Ks","4h","3d","2s","As"))));
+        assert!(best < v1);     // jack-four is still better
+
+        hand.set(hand!("Ts","7d","5c","4h","2s","As","3d"));
+        let v2 = ojp_ar_value(&hand);
+        let d2 = ojp_ar_description(&hand, v2);
+        assert_eq!(d2.level, HandLevel::UnqualifiedNoPair);
+        assert_eq!(d2.full_text(), "unqualified five-four");
+        assert!(v1 < v2);
+
+        Ok(())
+    }
+}
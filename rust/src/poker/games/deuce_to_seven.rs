@@ -46,6 +46,14 @@ pub fn ojp_kc_eval_5(h: &Hand) -> HandValue {
     ojp_reference_evaluator(h, Scale::DeuceToSeven)
 }
 
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_kc_eval_7) | Deuce-to-seven 7-card evaluator
+///
+/// Works for any hand of 5 or more cards without lookup tables.
+#[cfg(not(feature = "deuce-to-seven-tables"))]
+pub fn ojp_kc_eval_7(h: &Hand) -> HandValue {
+    ojp_lowball_evaluator(h, Scale::DeuceToSeven)
+}
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_kc_eval_5) | Deuce-to-seven 5-card evaluator
 #[cfg(feature = "deuce-to-seven-tables")]
 pub fn ojp_kc_eval_5(hand: &Hand) -> HandValue {
@@ -77,7 +85,7 @@ pub fn ojp_kc_value(h: &Hand) -> HandValue {
     match h.len() {
         ..5 => ojp_reference_evaluator(h, Scale::DeuceToSeven),
         5 => ojp_kc_eval_5(h),
-        6.. => ojp_best_of(h, 5, Scale::DeuceToSeven, ojp_kc_eval_5),
+        6.. => ojp_kc_eval_7(h),
     }
 }
 
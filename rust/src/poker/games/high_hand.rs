@@ -135,16 +135,38 @@ pub fn ojp_hb_full_text(d: &HandDescription) -> String {
 }
 
 /// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_hb_description) | High hand with bug description
+///
+/// The joker is replaced in the described hand by the card it plays as,
+/// which is also recorded in the description's extras.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::games::high_hand::*;
+///
+/// let hand = Hand::new(DeckType::OneJoker).init(hand!("Ac","As","Jk","Ad","Ah"));
+/// let v = ojp_hb_value(&hand);
+/// let d = ojp_hb_description(&hand, v);
+/// println!("[{}]: {}", d.hand_to_string(), d.full_text());
+/// // Output: "[AsAhAdAcAc]: five aces"
+/// ```
 #[cfg(not(feature = "high-hand-tables"))]
 pub fn ojp_hb_description(h: &Hand, v: HandValue) -> HandDescription {
-    HandDescription::from_value(h, Scale::HighHandBug, v)
+    hb_description(h, v)
 }
 
-/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_high_bug_description) | High hand with bug description
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_hb_description) | High hand with bug description
 #[cfg(feature = "high-hand-tables")]
 pub fn ojp_hb_description(h: &Hand, v: HandValue) -> HandDescription {
-    HandDescription::from_value(h, Scale::HighHandBug,
-        OJP_HH_TABLE_2[v as usize])
+    hb_description(h, OJP_HH_TABLE_2[v as usize])
+}
+
+fn hb_description(h: &Hand, v: HandValue) -> HandDescription {
+    let (bh, bug) = ojp_bug_replace(h, Scale::HighHandBug, ojp_hb_eval_5);
+    let mut d = HandDescription::from_value(&bh, Scale::HighHandBug, v);
+
+    if let Some(c) = bug {
+        d.add_bugs([c, Card::default()]);
+    }
+    d
 }
 
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_hb_eval_5) | 5-card high evaluator with bug
@@ -157,6 +179,19 @@ pub fn ojp_hb_eval_5(h: &Hand) -> HandValue {
     ojp_hh_eval_5(&bh)
 }
 
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_hb_eval_7) | 7-card high evaluator with bug
+///
+/// Hands without the joker go straight to the high hand evaluator (and
+/// its 7-card table if enabled); otherwise every 5-card subset is scanned.
+pub fn ojp_hb_eval_7(h: &Hand) -> HandValue {
+    debug_assert!(7 == h.len());
+
+    if ! h.contains(JOKER) {
+        return ojp_hh_value(h);
+    }
+    ojp_best_of(h, 5, Scale::HighHandBug, ojp_hb_eval_5)
+}
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_hb_value) | High hand with bug evaluator
 /// ```rust
 /// use onejoker::prelude::*;
@@ -178,7 +213,9 @@ pub fn ojp_hb_value(h: &Hand) -> u32 {
             ojp_hh_value(h)
         },
         5 => ojp_hb_eval_5(h),
-        6.. => ojp_best_of(h, 5, Scale::HighHandBug, ojp_hb_eval_5),
+        6 => ojp_best_of(h, 5, Scale::HighHandBug, ojp_hb_eval_5),
+        7 => ojp_hb_eval_7(h),
+        8.. => ojp_best_of(h, 7, Scale::HighHandBug, ojp_hb_eval_7),
     }
 }
 
@@ -483,6 +520,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_hb_description() -> Result<()> {
+        let deck = Deck::new_by_name("onejoker");
+        let mut hand= deck.new_hand();
+
+        fn bug(d: &HandDescription) -> Card {
+            match d.extras {
+                HandExtras::Bugs(b) => b[0],
+                _ => panic!("bug not recorded"),
+            }
+        }
+
+        hand.set(hand!("Ac","As","Jk","Ad","Ah"));
+        let mut v = ojp_hb_value(&hand);
+        let mut d = ojp_hb_description(&hand, v);
+        assert_eq!(d.level, HandLevel::FiveOfAKind);
+        assert_eq!(d.full_text(), "five aces");
+        assert_eq!(bug(&d).rank(), Rank::Ace);
+        assert!(! d.hand_to_string().contains("Jk"));
+
+        hand.set(hand!("Jk","9h","8d","Ts","6s"));
+        v = ojp_hb_value(&hand);
+        d = ojp_hb_description(&hand, v);
+        assert_eq!(d.full_text(), "ten-high straight");
+        assert_eq!(d.hand[3].rank(), Rank::Seven);
+        assert_eq!(bug(&d).rank(), Rank::Seven);
+
+        hand.set(hand!("5d","Td","8d","Ad","Jk"));
+        v = ojp_hb_value(&hand);
+        d = ojp_hb_description(&hand, v);
+        assert_eq!(d.full_text(), "flush: ace, king, ten, eight, five");
+        assert_eq!(d.hand[1], KING_OF_DIAMONDS);
+        assert_eq!(bug(&d), KING_OF_DIAMONDS);
+
+        hand.set(hand!("Qh","As","9h","Jk","Ad","Th","Jh"));
+        v = ojp_hb_value(&hand);
+        d = ojp_hb_description(&hand, v);
+        assert_eq!(d.level, HandLevel::StraightFlush);
+        assert_eq!(d.full_text(), "king-high straight flush");
+        assert_eq!(d.hand_to_string(), "KhQhJhTh9h");
+        assert_eq!(bug(&d), KING_OF_HEARTS);
+
+        hand.set(hand!("Qh","As","9h","Kc","Ad","Th","Jh"));
+        v = ojp_hb_value(&hand);
+        d = ojp_hb_description(&hand, v);
+        assert_eq!(v, ojp_hh_value(&hand));
+        assert_eq!(d.level, HandLevel::Straight);
+        assert!(matches!(d.extras, HandExtras::None));
+        Ok(())
+    }
+
     #[cfg(feature = "high-hand-tables")]
     #[test]
     fn test_tables() -> Result<()> {
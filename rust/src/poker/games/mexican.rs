@@ -0,0 +1,234 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Mexican) | Mexican poker hand values
+
+use crate::cards::*;
+use crate::poker::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_mx_full_text) | Describe Mexican poker hand
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::games::mexican::*;
+///
+/// let hand = Hand::new(DeckType::Mexican).init(hand!("Jd","7s","6c","Jk","4h"));
+/// let v = ojp_mx_value(&hand);
+/// let d = ojp_mx_description(&hand, v);
+/// println!("{}", ojp_mx_full_text(&d));
+/// // Output: "jack-high straight"
+/// ```
+pub fn ojp_mx_full_text(d: &HandDescription) -> String {
+    ojp_hh_full_text(d)
+}
+
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_mx_description) | Mexican poker hand description
+///
+/// The joker is replaced in the described hand by the card it plays as,
+/// which is also recorded in the description's extras.
+pub fn ojp_mx_description(h: &Hand, v: HandValue) -> HandDescription {
+    let (bh, bug) = ojp_bug_replace(h, Scale::Mexican, ojp_mx_eval_5);
+    let mut d = HandDescription::from_value(&bh, Scale::Mexican, v);
+
+    if let Some(c) = bug {
+        d.add_bugs([c, Card::default()]);
+    }
+    d
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_mx_eval_5) | 5-card Mexican poker evaluator
+pub fn ojp_mx_eval_5(h: &Hand) -> HandValue {
+    let Some(sr) = ojp_bug_scan_5_1(h, Scale::Mexican) else {
+        return ojp_reference_evaluator(h, Scale::Mexican);
+    };
+    let mut bh = *h;
+    bh[sr.index as usize] = sr.replacement;
+    ojp_reference_evaluator(&bh, Scale::Mexican)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_mx_value) | Mexican poker hand evaluator
+///
+/// 41-card deck with no 8s, 9s, or 10s, so 7-J are consecutive for
+/// straights and a flush beats a full house. The joker is a bug.
+/// ```rust
+/// use onejoker::prelude::*;
+///
+/// let h1 = Hand::new(DeckType::Mexican).init(hand!("Js","7s","2s","Ks","4s"));
+/// let h2 = Hand::new(DeckType::Mexican).init(hand!("Ah","Ac","Ad","Kd","Ks"));
+/// let v1 = Scale::Mexican.value(&h1);
+/// let v2 = Scale::Mexican.value(&h2);
+/// assert!(v1 < v2);   // flush beats full house
+/// ```
+pub fn ojp_mx_value(h: &Hand) -> HandValue {
+    debug_assert!(Scale::Mexican.valid_hand(h));
+
+    match h.len() {
+        ..5 => {
+            if let Some(sr) = ojp_bug_scan_p_1(h, Scale::Mexican) {
+                let mut bh = *h;
+                bh[sr.index as usize] = sr.replacement;
+                return ojp_reference_evaluator(&bh, Scale::Mexican);
+            }
+            ojp_reference_evaluator(h, Scale::Mexican)
+        },
+        5 => ojp_mx_eval_5(h),
+        6.. => ojp_best_of(h, 5, Scale::Mexican, ojp_mx_eval_5),
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_hand_evaluator_mx() -> Result<()> {
+        let deck = Deck::new_by_name("mexican");
+        let mut hand= deck.new_hand();
+        let mut best: u32 = HAND_VALUE_WORST;
+
+        hand.set(hand!("Jd","3d","Qc","As","6h"));
+        let mut v1 = ojp_mx_value(&hand);
+        let mut d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+
+        hand.set(hand!("Qc","Jk","Jd","3h","6s"));
+        let mut v2 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v2);
+        assert_eq!(v1, v2);
+        assert_eq!(d1.hand[0].rank(), Rank::Ace);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("6h","2d","Jc","6d","Jk"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Pair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("4h","Kc","Kd","Jk","4c"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::TwoPair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("5h","Jk","5c","5s","Kd"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Trips);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ah","Jk","3c","4s","2d"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::Five);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("6c","Jk","3c","4s","5d"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::Seven);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Jd","7s","6c","5h","4h"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.full_text(), "jack-high straight");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Jd","7s","6c","Jk","4h"));
+        v2 = ojp_mx_value(&hand);
+        assert_eq!(v1, v2);
+
+        hand.set(hand!("Jd","Qs","6c","Jk","7h"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::King);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Kd","As","Js","7h","Qh"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("7s","7h","Jk","7d","Ad"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::FullHouse);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("5d","Qd","2d","Jk","Kd"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Flush);
+        assert_eq!(d1.hand[0], ACE_OF_DIAMONDS);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Kc","Qc","Jk","Ac","Jc"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert_eq!(d1.hand[4], SEVEN_OF_CLUBS);
+        assert_eq!(d1.full_text(), "royal flush");
+
+        hand.set(hand!("3c","3s","3d","3h","Jk"));
+        v2 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v2);
+        assert_eq!(d1.level, HandLevel::Quads);
+        assert!(v2 < best);
+        assert!(v1 < v2);
+        best = v2;
+
+        hand.set(hand!("Ac","As","Jk","Ad","Ah"));
+        v2 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v2);
+        assert_eq!(d1.level, HandLevel::FiveOfAKind);
+        assert!(v2 < best);
+
+        hand.set(hand!("3s","Ac","Jk","2c","Kc","5d","7c"));
+        v1 = ojp_mx_value(&hand);
+        d1 = ojp_mx_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Flush);
+        assert_eq!(d1.hand[2], QUEEN_OF_CLUBS);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mexican_bug_straights() -> Result<()> {
+        let deck = Deck::new_by_name("mexican");
+        let mut hand= deck.new_hand();
+
+        // The bug never plays as a card missing from the deck
+        for cards in [
+            hand!("7c","Jd","Qh","Jk","As"),
+            hand!("7c","6d","5h","Jk","Qs"),
+            hand!("Kh","Qh","Jh","Jk","Ah"),
+            hand!("Kh","Qh","Jh","Jk","7h"),
+            hand!("7h","Qh","Jh","Jk","2h"),
+        ] {
+            hand.set(cards);
+            let v = ojp_mx_value(&hand);
+            let d = ojp_mx_description(&hand, v);
+            for i in 0..5 {
+                assert!(deck.deck_type().has(d.hand[i]));
+            }
+        }
+        Ok(())
+    }
+}
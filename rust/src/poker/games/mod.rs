@@ -14,12 +14,17 @@ pub use ace_to_six::*;
 pub mod badugi;
 pub use badugi::*;
 
-// pub mod pai_gow;
-// pub use pai_gow::*;
+pub mod pai_gow;
+pub use pai_gow::*;
 
-// pub mod stripped_deck;
-// pub use stripped_deck::*;
+pub mod stripped_deck;
+pub use stripped_deck::*;
 
-// pub mod action_razz;
-// pub use action_razz::*;
+pub mod action_razz;
+pub use action_razz::*;
 
+pub mod mexican;
+pub use mexican::*;
+
+pub mod three_card;
+pub use three_card::*;
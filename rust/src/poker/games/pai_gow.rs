@@ -0,0 +1,271 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/PaiGow) | Pai gow poker hand values
+
+use crate::cards::*;
+use crate::poker::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_pg_full_text) | Describe pai gow hand
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::games::pai_gow::*;
+///
+/// let hand = Hand::new(DeckType::OneJoker).init(hand!("3c","Jk","4d","5h","2s"));
+/// let v = ojp_pg_value(&hand);
+/// let d = ojp_pg_description(&hand, v);
+/// println!("{}", ojp_pg_full_text(&d));
+/// // Output: "five-high straight" (ranks above king-high)
+/// ```
+pub fn ojp_pg_full_text(d: &HandDescription) -> String {
+    macro_rules! sng {
+        ($x:literal) => { d.hand[$x as usize].rank().name() }
+    }
+    // The wheel is not rotated in pai gow, so the ace stays on top
+    let wheel = d.hand[0].rank() == Rank::Ace && d.hand[1].rank() == Rank::Five;
+
+    match d.level {
+        HandLevel::StraightFlush if wheel => {
+            format!("{}-high straight flush", sng!(1))
+        },
+        HandLevel::Straight if wheel => {
+            format!("{}-high straight", sng!(1))
+        },
+        _ => ojp_hh_full_text(d)
+    }
+}
+
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_pg_description) | Pai gow hand description
+///
+/// The joker is replaced in the described hand by the card it plays as,
+/// which is also recorded in the description's extras.
+pub fn ojp_pg_description(h: &Hand, v: HandValue) -> HandDescription {
+    let (bh, bug) = ojp_bug_replace(h, Scale::PaiGow, ojp_pg_eval_5);
+    let mut d = HandDescription::from_value(&bh, Scale::PaiGow, v);
+
+    if let Some(c) = bug {
+        d.add_bugs([c, Card::default()]);
+    }
+    d
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_pg_eval_5) | 5-card pai gow evaluator
+pub fn ojp_pg_eval_5(h: &Hand) -> HandValue {
+    let Some(sr) = ojp_bug_scan_5_1(h, Scale::PaiGow) else {
+        return ojp_reference_evaluator(h, Scale::PaiGow);
+    };
+    let mut bh = *h;
+    bh[sr.index as usize] = sr.replacement;
+    ojp_reference_evaluator(&bh, Scale::PaiGow)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_pg_value) | Pai gow hand evaluator
+/// ```rust
+/// use onejoker::prelude::*;
+///
+/// let h1 = Hand::new(DeckType::OneJoker).init(hand!("Ah","2c","3d","4s","5h"));
+/// let h2 = Hand::new(DeckType::OneJoker).init(hand!("Kc","Qd","Js","Th","9c"));
+/// let v1 = Scale::PaiGow.value(&h1);
+/// let v2 = Scale::PaiGow.value(&h2);
+/// assert!(v1 < v2);   // wheel beats king-high straight
+/// ```
+pub fn ojp_pg_value(h: &Hand) -> HandValue {
+    debug_assert!(Scale::PaiGow.valid_hand(h));
+
+    match h.len() {
+        ..5 => {
+            if let Some(sr) = ojp_bug_scan_p_1(h, Scale::PaiGow) {
+                let mut bh = *h;
+                bh[sr.index as usize] = sr.replacement;
+                return ojp_reference_evaluator(&bh, Scale::PaiGow);
+            }
+            ojp_reference_evaluator(h, Scale::PaiGow)
+        },
+        5 => ojp_pg_eval_5(h),
+        6.. => ojp_best_of(h, 5, Scale::PaiGow, ojp_pg_eval_5),
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_hand_evaluator_pg() -> Result<()> {
+        let deck = Deck::new_by_name("paigow");
+        let mut hand= deck.new_hand();
+        let mut best: u32 = HAND_VALUE_WORST;
+
+        hand.set(hand!("9d","3d","Qc","As","Th"));
+        let mut v1 = ojp_pg_value(&hand);
+        let mut d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+
+        hand.set(hand!("Qc","9s","Jk","Td","3h"));
+        let mut v2 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v2);
+        assert_eq!(v1, v2);
+        assert_eq!(d1.hand[0].rank(), Rank::Ace);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("6h","2d","9c","6d","Jk"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Pair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ah","Jk","Kd","2c","7h"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Pair);
+        assert_eq!(d1.hand[0].rank(), Rank::Ace);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("5h","Jk","5c","5s","Kd"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Trips);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("5h","Ac","9h","6d","5c","5s","Ks"));
+        v2 = ojp_pg_value(&hand);
+        assert_eq!(v1, v2);
+
+        hand.set(hand!("9c","Jk","Tc","6c","8h"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::Ten);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Kd","Qc","Js","Th","9c"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::King);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ah","2c","3d","4s","5h"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::Ace);
+        assert_eq!(d1.hand[1].rank(), Rank::Five);
+        assert_eq!(d1.full_text(), "five-high straight");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("2c","3d","Jk","5h","4s"));
+        v2 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v2);
+        assert_eq!(v1, v2);
+        assert_eq!(d1.hand[0].rank(), Rank::Ace);
+
+        hand.set(hand!("3c","6d","Ah","2c","Kh","4s","5h"));
+        v2 = ojp_pg_value(&hand);
+        assert_eq!(v1, v2);
+
+        hand.set(hand!("Kd","Ac","Js","Th","Qc"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.full_text(), "ace-high straight");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("5d","Td","8d","Jk","Qd"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Flush);
+        assert_eq!(d1.hand[0], ACE_OF_DIAMONDS);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("7s","7h","Jk","7d","Ad"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::FullHouse);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("3c","3s","3d","3h","Jk"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Quads);
+        assert_eq!(d1.hand[4].rank(), Rank::Ace);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Kd","Jd","Jk","Td","9d"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert_eq!(d1.hand[0].rank(), Rank::King);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("4h","2h","Jk","3h","5h"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert_eq!(d1.hand[0], ACE_OF_HEARTS);
+        assert_eq!(d1.full_text(), "five-high straight flush");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ts","Qs","Jk","Js","Ks"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert_eq!(d1.full_text(), "royal flush");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ac","As","Jk","Ad","Ah"));
+        v1 = ojp_pg_value(&hand);
+        d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::FiveOfAKind);
+        assert_eq!(d1.full_text(), "five aces");
+        assert!(v1 < best);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pai_gow_two_card() -> Result<()> {
+        let deck = Deck::new_by_name("paigow");
+        let mut hand= deck.new_hand();
+
+        hand.set(hand!("Kd","Qc"));
+        let v1 = ojp_pg_value(&hand);
+        let d1 = ojp_pg_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+
+        hand.set(hand!("Jk","2c"));
+        let v2 = ojp_pg_value(&hand);
+        let d2 = ojp_pg_description(&hand, v2);
+        assert_eq!(d2.level, HandLevel::NoPair);
+        assert_eq!(d2.hand[0].rank(), Rank::Ace);
+        assert!(v2 < v1);
+
+        hand.set(hand!("Ah","Jk"));
+        let v3 = ojp_pg_value(&hand);
+        let d3 = ojp_pg_description(&hand, v3);
+        assert_eq!(d3.level, HandLevel::Pair);
+        assert!(v3 < v2);
+
+        match d3.extras {
+            HandExtras::Bugs(b) => assert_eq!(b[0], ACE_OF_SPADES),
+            _ => panic!("bug not recorded"),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Stripped) | Stripped-deck and short-deck hand values
+
+use crate::cards::*;
+use crate::poker::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_st_full_text) | Describe stripped-deck hand
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::games::stripped_deck::*;
+///
+/// let hand = Hand::new(DeckType::Swiss).init(hand!("9s","As","7d","8s","6h"));
+/// let v = ojp_sd_value(&hand);
+/// let d = ojp_sd_description(&hand, v);
+/// println!("{}", ojp_st_full_text(&d));
+/// // Output: "nine-high straight"
+/// ```
+pub fn ojp_st_full_text(d: &HandDescription) -> String {
+    ojp_hh_full_text(d)
+}
+
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_st_description) | Stripped-deck hand description
+pub fn ojp_st_description(h: &Hand, v: HandValue) -> HandDescription {
+    HandDescription::from_value(h, Scale::Stripped, v)
+}
+
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_sd_description) | Short-deck hand description
+pub fn ojp_sd_description(h: &Hand, v: HandValue) -> HandDescription {
+    HandDescription::from_value(h, Scale::ShortDeck, v)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_st_eval_5) | 5-card stripped-deck evaluator
+pub fn ojp_st_eval_5(h: &Hand) -> HandValue {
+    ojp_reference_evaluator(h, Scale::Stripped)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_sd_eval_5) | 5-card short-deck evaluator
+pub fn ojp_sd_eval_5(h: &Hand) -> HandValue {
+    ojp_reference_evaluator(h, Scale::ShortDeck)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_st_value) | Stripped-deck hand evaluator
+///
+/// Flush beats full house, straight beats trips.
+/// ```rust
+/// use onejoker::prelude::*;
+///
+/// let h1 = Hand::new(DeckType::Stripped).init(hand!("Js","8s","9s","Ks","7s"));
+/// let h2 = Hand::new(DeckType::Stripped).init(hand!("Ah","Ac","Ad","Kd","Ks"));
+/// let v1 = Scale::Stripped.value(&h1);
+/// let v2 = Scale::Stripped.value(&h2);
+/// assert!(v1 < v2);   // flush beats full house
+/// ```
+pub fn ojp_st_value(h: &Hand) -> HandValue {
+    debug_assert!(Scale::Stripped.valid_hand(h));
+
+    match h.len() {
+        ..5 => ojp_reference_evaluator(h, Scale::Stripped),
+        5 => ojp_st_eval_5(h),
+        6.. => ojp_best_of(h, 5, Scale::Stripped, ojp_st_eval_5),
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_sd_value) | Short-deck hand evaluator
+///
+/// Flush beats full house, trips beat straight.
+/// ```rust
+/// use onejoker::prelude::*;
+///
+/// let h1 = Hand::new(DeckType::Swiss).init(hand!("7h","7c","7d","Kd","Qs"));
+/// let h2 = Hand::new(DeckType::Swiss).init(hand!("Ah","Kc","Qd","Jd","Ts"));
+/// let v1 = Scale::ShortDeck.value(&h1);
+/// let v2 = Scale::ShortDeck.value(&h2);
+/// assert!(v1 < v2);   // trips beat straight
+/// ```
+pub fn ojp_sd_value(h: &Hand) -> HandValue {
+    debug_assert!(Scale::ShortDeck.valid_hand(h));
+
+    match h.len() {
+        ..5 => ojp_reference_evaluator(h, Scale::ShortDeck),
+        5 => ojp_sd_eval_5(h),
+        6.. => ojp_best_of(h, 5, Scale::ShortDeck, ojp_sd_eval_5),
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_hand_evaluator_sd() -> Result<()> {
+        let deck = Deck::new_by_name("swiss");
+        let mut hand= deck.new_hand();
+        let mut best = HAND_VALUE_WORST;
+
+        hand.set(hand!("9d","6d","Qc","Kc","Th"));
+        let mut v1 = ojp_sd_value(&hand);
+        let mut d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("6h","Ad","9c","6d","Ts"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Pair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Jh","8c","8d","Ad","Jc"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::TwoPair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("As","7h","6s","8d","9c"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::Nine);
+        assert_eq!(d1.hand[4].rank(), Rank::Ace);
+        assert_eq!(d1.full_text(), "nine-high straight");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ks","7h","6s","Ac","9c","8d","Kd"));
+        let mut v2 = ojp_sd_value(&hand);
+        assert_eq!(v1, v2);
+
+        hand.set(hand!("Td","7h","6s","8d","9c"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Kd","As","Js","Th","Qh"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("6h","6d","Kc","6s","7d"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Trips);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ac","As","7d","7h","Ah"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::FullHouse);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("6d","Td","8d","7d","Qd"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Flush);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("6d","Td","8d","7d","Qd","Qs","Qc"));
+        v2 = ojp_sd_value(&hand);
+        assert_eq!(v1, v2);
+
+        hand.set(hand!("7c","7s","7d","7h","Kd"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Quads);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("9h","Ah","6h","7h","8h"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert_eq!(d1.full_text(), "nine-high straight flush");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ts","Qs","As","Js","Ks"));
+        v1 = ojp_sd_value(&hand);
+        d1 = ojp_sd_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert_eq!(d1.full_text(), "royal flush");
+        assert!(v1 < best);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hand_evaluator_st() -> Result<()> {
+        let deck = Deck::new_by_name("stripped");
+        let mut hand= deck.new_hand();
+
+        hand.set(hand!("Kh","Kc","Kd","9d","Qs"));
+        let v1 = ojp_st_value(&hand);
+        let d1 = ojp_st_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Trips);
+
+        hand.set(hand!("Ah","7c","8d","9d","Ts"));
+        let v2 = ojp_st_value(&hand);
+        let d2 = ojp_st_description(&hand, v2);
+        assert_eq!(d2.level, HandLevel::Straight);
+        assert_eq!(d2.hand[0].rank(), Rank::Ten);
+        assert!(v2 < v1);
+
+        hand.set(hand!("Jh","Tc","8d","9d","7s"));
+        let v3 = ojp_st_value(&hand);
+        assert!(v3 < v2);
+
+        // No A-6-7-8-9 straight without the sixes
+        hand.set(hand!("Ah","7c","8d","9d","Js"));
+        let v4 = ojp_st_value(&hand);
+        let d4 = ojp_st_description(&hand, v4);
+        assert_eq!(d4.level, HandLevel::NoPair);
+
+        Ok(())
+    }
+
+    // Independent ordering key for a 5-card hand: lower is better
+    fn reference_key(h: &Hand, trips_over_straight: bool, low: u8) -> Vec<u8> {
+        // Ordinal without the knight gap, so J-Q are adjacent
+        let ord = |r: Rank| -> u8 {
+            if r > Rank::Knight { r as u8 - 1 } else { r as u8 }
+        };
+        let mut counts = [0u8; 16];
+        for c in h {
+            counts[ord(c.rank()) as usize] += 1;
+        }
+        let flush = h[..].iter().all(|c| c.suit() == h[0].suit());
+        let distinct: Vec<u8> = (0..16u8).rev()
+            .filter(|&o| counts[o as usize] > 0).collect();
+
+        let mut top: Option<u8> = None;
+        if 5 == distinct.len() {
+            if distinct[0] - distinct[4] == 4 {
+                top = Some(distinct[0]);
+            } else if distinct[0] == ord(Rank::Ace) &&
+                distinct[1] == low + 3 && distinct[4] == low {
+                top = Some(low + 3);
+            }
+        }
+        let mut groups: Vec<(u8, u8)> = distinct.iter()
+            .map(|&o| (counts[o as usize], o)).collect();
+        groups.sort_by(|a, b| b.cmp(a));
+
+        let (st, tr) = if trips_over_straight { (5, 4) } else { (4, 5) };
+        let cat = if top.is_some() && flush { 0 }
+            else if 4 == groups[0].0 { 1 }
+            else if flush { 2 }
+            else if 3 == groups[0].0 && 2 == groups[1].0 { 3 }
+            else if top.is_some() { st }
+            else if 3 == groups[0].0 { tr }
+            else if 2 == groups[0].0 && 2 == groups[1].0 { 6 }
+            else if 2 == groups[0].0 { 7 }
+            else { 8 };
+
+        let mut key = vec![cat];
+        if let Some(t) = top {
+            key.push(20 - t);
+        } else {
+            key.extend(groups.iter().map(|g| 20 - g.1));
+        }
+        key
+    }
+
+    fn check_against_reference(dname: &str, g: Scale, eval: HandEvaluator,
+    trips_over_straight: bool, low: u8, expected: &[usize; 10]) {
+        let deck = Deck::new_by_name(dname);
+        let mut results: Vec<(HandValue, Vec<u8>)> = Vec::new();
+        let mut counts = [0usize; 10];
+
+        for h in deck.combinations(5) {
+            let v = eval(&h);
+            let l = g.level_from_value(v / HAND_LEVEL_MULTIPLIER);
+            counts[g.value_from_level(l) as usize - 1] += 1;
+            results.push((v, reference_key(&h, trips_over_straight, low)));
+        }
+        assert_eq!(&counts, expected);
+
+        results.sort();
+        for i in 1..results.len() {
+            if results[i].0 == results[i - 1].0 {
+                assert_eq!(results[i].1, results[i - 1].1);
+            } else {
+                assert!(results[i].1 > results[i - 1].1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_deck_enumerated() -> Result<()> {
+        check_against_reference("swiss", Scale::ShortDeck, ojp_sd_eval_5,
+            true, Rank::Six as u8,
+            &[0, 24, 288, 480, 1728, 16128, 6120, 36288, 193536, 122400]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stripped_deck_enumerated() -> Result<()> {
+        check_against_reference("stripped", Scale::Stripped, ojp_st_eval_5,
+            false, Rank::Seven as u8,
+            &[0, 20, 224, 204, 1344, 5100, 10752, 24192, 107520, 52020]);
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/ThreeCard) | Three-card poker hand values
+
+use crate::cards::*;
+use crate::poker::*;
+
+#[cfg(feature = "three-card-tables")]
+use crate::poker::tables::three_card_tables::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_tc_full_text) | Describe three-card hand
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::games::three_card::*;
+///
+/// let hand = Hand::new(DeckType::English).init(hand!("9s","Jd","9c"));
+/// let v = ojp_tc_value(&hand);
+/// let d = ojp_tc_description(&hand, v);
+/// println!("{}", ojp_tc_full_text(&d));
+/// // Output: "pair of nines, jack"
+/// ```
+pub fn ojp_tc_full_text(d: &HandDescription) -> String {
+    macro_rules! sng {
+        ($x:literal) => { d.hand[$x as usize].rank().name() }
+    }
+    macro_rules! plr {
+        ($x:literal) => { d.hand[$x as usize].rank().plural() }
+    }
+
+    match d.level {
+        HandLevel::Trips => {
+            format!("three {}", plr!(0))
+        },
+        HandLevel::StraightFlush => {
+            format!("{}-high straight flush", sng!(0))
+        },
+        HandLevel::Straight => {
+            format!("{}-high straight", sng!(0))
+        },
+        HandLevel::Flush => {
+            format!("flush: {}, {}, {}", sng!(0), sng!(1), sng!(2))
+        },
+        HandLevel::Pair => {
+            format!("pair of {}, {}", plr!(0), sng!(2))
+        },
+        HandLevel::NoPair => {
+            format!("no pair: {}, {}, {}", sng!(0), sng!(1), sng!(2))
+        },
+        _ => String::from("unknown hand"),
+    }
+}
+
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_tc_description) | Three-card hand description
+#[cfg(not(feature = "three-card-tables"))]
+pub fn ojp_tc_description(h: &Hand, v: HandValue) -> HandDescription {
+    HandDescription::from_value(h, Scale::ThreeCard, v)
+}
+
+/// [wiki](https:://github.com/lcrocker/ojpoker/wiki/ojp_tc_description) | Three-card hand description
+#[cfg(feature = "three-card-tables")]
+pub fn ojp_tc_description(h: &Hand, v: HandValue) -> HandDescription {
+    // Partial hands are valued by the reference evaluator, not the table
+    if h.len() < 3 {
+        return HandDescription::from_value(h, Scale::ThreeCard, v);
+    }
+    HandDescription::from_value(h, Scale::ThreeCard,
+        OJP_TC_TABLE_2[v as usize])
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_tc_eval_3) | 3-card poker hand evaluator
+#[cfg(not(feature = "three-card-tables"))]
+pub fn ojp_tc_eval_3(h: &Hand) -> HandValue {
+    ojp_reference_evaluator(h, Scale::ThreeCard)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_tc_eval_3) | 3-card poker hand evaluator
+#[cfg(feature = "three-card-tables")]
+pub fn ojp_tc_eval_3(h: &Hand) -> HandValue {
+    let hash = ojh_bitfield_mp3_english(&h[..3]);
+    OJP_TC_TABLE_1[hash as usize] as HandValue
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_tc_value) | Three-card poker hand evaluator
+///
+/// Straights beat flushes, and trips beat straight flushes. A-2-3 is
+/// the lowest straight. Larger hands play their best three cards.
+/// ```rust
+/// use onejoker::prelude::*;
+///
+/// let h1 = Hand::new(DeckType::English).init(hand!("4c","2d","3h"));
+/// let h2 = Hand::new(DeckType::English).init(hand!("Ks","Js","9s"));
+/// let v1 = Scale::ThreeCard.value(&h1);
+/// let v2 = Scale::ThreeCard.value(&h2);
+/// assert!(v1 < v2);   // straight beats flush
+/// ```
+pub fn ojp_tc_value(h: &Hand) -> HandValue {
+    debug_assert!(Scale::ThreeCard.valid_hand(h));
+
+    match h.len() {
+        ..3 => ojp_reference_evaluator(h, Scale::ThreeCard),
+        3 => ojp_tc_eval_3(h),
+        4.. => ojp_best_of(h, 3, Scale::ThreeCard, ojp_tc_eval_3),
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_hand_evaluator_tc() -> Result<()> {
+        let deck = Deck::new_by_name("english");
+        let mut hand= deck.new_hand();
+        let mut best: u32 = HAND_VALUE_WORST;
+
+        hand.set(hand!("2c","4h","5d"));
+        let mut v1 = ojp_tc_value(&hand);
+        let mut d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Kd","Qc","9h"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert_eq!(d1.full_text(), "no pair: king, queen, nine");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("9s","Jd","9c"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Pair);
+        assert_eq!(d1.full_text(), "pair of nines, jack");
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("2d","9d","Kd"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Flush);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Ah","9d","Ad","5c","Kd","2d","Jd"));
+        let mut v2 = ojp_tc_value(&hand);
+        let d2 = ojp_tc_description(&hand, v2);
+        assert_eq!(d2.level, HandLevel::Flush);
+        assert_eq!(d2.hand[0], ACE_OF_DIAMONDS);
+        assert!(v2 < v1);
+
+        hand.set(hand!("Ac","2d","3h"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.full_text(), "trey-high straight");
+        assert!(v1 < best);
+        assert!(v1 < v2);
+        best = v1;
+
+        hand.set(hand!("Kc","Ad","Qh"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("4s","2s","3s"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("Qh","Kh","Ah"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::StraightFlush);
+        assert!(v1 < best);
+        best = v1;
+
+        hand.set(hand!("2h","2c","2d"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Trips);
+        assert_eq!(d1.full_text(), "three deuces");
+        assert!(v1 < best);
+
+        hand.set(hand!("Jc","2h","Jd","2c","Qc","2d","Kc"));
+        v2 = ojp_tc_value(&hand);
+        assert_eq!(v1, v2);
+
+        // Straight from extra cards beats a flush
+        hand.set(hand!("Jc","2h","Qc","Kd","8c","5h","3s"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+        assert_eq!(d1.hand[0].rank(), Rank::King);
+
+        hand.set(hand!("Jc","2h","Qc","Kh","8c","5h","3h"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Straight);
+
+        hand.set(hand!("Jc","2h","Qc","9h","8c","5h","3h"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Flush);
+        assert_eq!(d1.hand[0], QUEEN_OF_CLUBS);
+        assert_eq!(d1.hand[1], JACK_OF_CLUBS);
+        assert_eq!(d1.hand[2], EIGHT_OF_CLUBS);
+
+        // Partial hands
+        hand.set(hand!("9s","9c"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::Pair);
+
+        hand.set(hand!("Kd","4c"));
+        v1 = ojp_tc_value(&hand);
+        d1 = ojp_tc_description(&hand, v1);
+        assert_eq!(d1.level, HandLevel::NoPair);
+        assert_eq!(d1.hand[0].rank(), Rank::King);
+
+        Ok(())
+    }
+}
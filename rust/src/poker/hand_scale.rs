@@ -1,5 +1,8 @@
 //! [wiki](https://github.com/lcrocker/ojpoker/wiki/Hand_Scale) | Poker hand types
 
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
 use crate::cards::*;
 use crate::poker::*;
 
@@ -9,6 +12,7 @@ use crate::poker::*;
 /// evaluated in different games.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Scale {
     /// None / Invalid
     None = 0,
@@ -37,11 +41,13 @@ pub enum Scale {
     AceToFiveBug = 11,
     /// Spanish deck with single bug
     Mexican = 12,
-    /// Three-card high hands
+    /// Three-card hands: straight beats flush, trips beat straight flush
     ThreeCard = 13,
+    /// Short-deck hold'em: flush beats full house, trips beat straight
+    ShortDeck = 14,
 }
 
-const SCALE_MAX: usize = Scale::ThreeCard as usize;
+const SCALE_MAX: usize = Scale::ShortDeck as usize;
 
 impl Scale {
     /// Get a hand scale by index
@@ -60,6 +66,7 @@ impl Scale {
             11 => Scale::AceToFiveBug,
             12 => Scale::Mexican,
             13 => Scale::ThreeCard,
+            14 => Scale::ShortDeck,
             _ => Scale::None,
         }
     }
@@ -177,6 +184,17 @@ impl Scale {
         SCALE_INFO_TABLE[*self as usize - 1].low_broadway
     }
 
+    /// Does ace play below the lowest rank of a stripped deck?
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// assert!(Scale::ShortDeck.short_wheel());   // A-6-7-8-9 is a straight
+    /// assert!(! Scale::HighHand.short_wheel());
+    /// ```
+    pub const fn short_wheel(&self) -> bool {
+        SCALE_INFO_TABLE[*self as usize - 1].short_wheel
+    }
+
     /// Does deck have 8s, 9s, and 10s removed?
     /// ```rust
     /// use onejoker::prelude::*;
@@ -210,6 +228,9 @@ impl Scale {
             Scale::Stripped | Scale::Mexican => {
                 st_value_from_level(hl)
             },
+            Scale::ShortDeck => {
+                sd_value_from_level(hl)
+            },
             Scale::ActionRazz => {
                 ar_value_from_level(hl)
             },
@@ -243,6 +264,9 @@ impl Scale {
             Scale::Stripped | Scale::Mexican => {
                 st_level_from_value(v)
             },
+            Scale::ShortDeck => {
+                sd_level_from_value(v)
+            },
             Scale::ActionRazz => {
                 ar_level_from_value(v)
             },
@@ -303,10 +327,21 @@ impl Scale {
     /// ```
     pub fn full_text(&self, d: &HandDescription) -> String {
         match *self {
-            Scale::HighHand | Scale::PaiGow | Scale::Stripped |
-            Scale::Mexican | Scale::ThreeCard => {
+            Scale::HighHand => {
                 ojp_hh_full_text(d)
             },
+            Scale::ThreeCard => {
+                ojp_tc_full_text(d)
+            },
+            Scale::Mexican => {
+                ojp_mx_full_text(d)
+            },
+            Scale::Stripped | Scale::ShortDeck => {
+                ojp_st_full_text(d)
+            },
+            Scale::PaiGow => {
+                ojp_pg_full_text(d)
+            },
             Scale::HighHandBug => {
                 ojp_hb_full_text(d)
             },
@@ -323,8 +358,7 @@ impl Scale {
                 ojp_bg_full_text(d)
             },
             Scale::ActionRazz => {
-                // ojp_ar_full_name(d)
-                ojp_a5_full_text(d)
+                ojp_ar_full_text(d)
             },
             Scale::None => "".to_string(),
         }
@@ -339,53 +373,37 @@ impl Scale {
     /// println!("{}", v);
     /// ```
     pub fn value(&self, hand: &Hand) -> HandValue {
+        (self.evaluator())(hand)
+    }
+
+    /// Evaluation function used by [Scale::value]
+    ///
+    /// Look it up once to evaluate many hands without matching on the
+    /// scale each time.
+    /// ```rust
+    /// use onejoker::prelude::*;
+    ///
+    /// let hand = Hand::new(DeckType::English).init(hand!("9s","As","9d","Ks","Ah"));
+    /// let eval = Scale::HighHand.evaluator();
+    /// assert_eq!(eval(&hand), Scale::HighHand.value(&hand));
+    /// ```
+    pub fn evaluator(&self) -> HandEvaluator {
         match *self {
-            Scale::HighHand => {
-                ojp_hh_value(hand)
-            },
-            Scale::AceToFive => {
-                ojp_a5_value(hand)
-            },
-            Scale::DeuceToSeven => {
-                ojp_kc_value(hand)
-            },
-            Scale::AceToSix => {
-                ojp_ll_value(hand)
-            },
-            Scale::Badugi => {
-                ojp_bg_value(hand)
-            },
-            Scale::Badeucy => {
-                ojp_bc_value(hand)
-            },
-            Scale::HighHandBug => {
-                ojp_hb_value(hand)
-            },
-            Scale::AceToFiveBug => {
-                // ojp_cl_value(hand)
-                HAND_VALUE_WORST
-            },
-            Scale::PaiGow => {
-                // ojp_pg_value(hand)
-                HAND_VALUE_WORST
-            },
-            Scale::Stripped => {
-                // ojp_st_value(hand)
-                HAND_VALUE_WORST
-            },
-            Scale::Mexican => {
-                // ojp_mx_value(hand)
-                HAND_VALUE_WORST
-            },
-            Scale::ActionRazz => {
-                // ojp_ar_value(hand)
-                HAND_VALUE_WORST
-            },
-            Scale::ThreeCard => {
-                // ojp_tc_value(hand)
-                HAND_VALUE_WORST
-            },
-            Scale::None => HAND_VALUE_WORST,
+            Scale::HighHand => ojp_hh_value,
+            Scale::AceToFive => ojp_a5_value,
+            Scale::DeuceToSeven => ojp_kc_value,
+            Scale::AceToSix => ojp_ll_value,
+            Scale::Badugi => ojp_bg_value,
+            Scale::Badeucy => ojp_bc_value,
+            Scale::HighHandBug => ojp_hb_value,
+            Scale::AceToFiveBug => ojp_cl_value,
+            Scale::PaiGow => ojp_pg_value,
+            Scale::Stripped => ojp_st_value,
+            Scale::ShortDeck => ojp_sd_value,
+            Scale::Mexican => ojp_mx_value,
+            Scale::ActionRazz => ojp_ar_value,
+            Scale::ThreeCard => ojp_tc_value,
+            Scale::None => |_| HAND_VALUE_WORST,
         }
     }
 
@@ -419,32 +437,28 @@ impl Scale {
                 ojp_bc_description(h, v)
             },
             Scale::HighHandBug => {
-                // ojp_hb_description(h, v)
-                ojp_hh_description(h, v)
+                ojp_hb_description(h, v)
             },
             Scale::AceToFiveBug => {
-                // ojp_cl_description(hand)
-                ojp_a5_description(h, v)
+                ojp_cl_description(h, v)
             },
             Scale::PaiGow => {
-                // ojp_pg_description(hand)
-                ojp_hh_description(h, v)
+                ojp_pg_description(h, v)
             },
             Scale::Stripped => {
-                // ojp_st_description(hand)
-                ojp_hh_description(h, v)
+                ojp_st_description(h, v)
+            },
+            Scale::ShortDeck => {
+                ojp_sd_description(h, v)
             },
             Scale::Mexican => {
-                // ojp_mx_description(hand)
-                ojp_hh_description(h, v)
+                ojp_mx_description(h, v)
             },
             Scale::ActionRazz => {
-                // ojp_ar_description(hand)
-                ojp_a5_description(h, v)
+                ojp_ar_description(h, v)
             },
             Scale::ThreeCard => {
-                // ojp_tc_deswcription(hand)
-                ojp_hh_description(h, v)
+                ojp_tc_description(h, v)
             },
             Scale::None => HandDescription::default(),
         }
@@ -478,6 +492,8 @@ struct ScaleInfo {
     pai_gow_wheel: bool,
     /// Is Broadway a straight for low-ace games?
     low_broadway: bool,
+    /// Ace plays below the lowest rank of a stripped deck
+    short_wheel: bool,
     /// 8s, 9s, and 10s removed
     spanish_gap: bool,
 }
@@ -634,6 +650,40 @@ const fn st_level_from_value(v: u32) -> HandLevel {
     }
 }
 
+#[inline]
+const fn sd_value_from_level(l: HandLevel) -> u32 {
+    match l {
+        HandLevel::FiveOfAKind => 1,
+        HandLevel::StraightFlush => 2,
+        HandLevel::Quads => 3,
+        HandLevel::Flush => 4,
+        HandLevel::FullHouse => 5,
+        HandLevel::Trips => 6,
+        HandLevel::Straight => 7,
+        HandLevel::TwoPair => 8,
+        HandLevel::Pair => 9,
+        HandLevel::NoPair => 10,
+        _ => HAND_LEVEL_WORST as u32,
+    }
+}
+
+#[inline]
+const fn sd_level_from_value(v: u32) -> HandLevel {
+    match v {
+        1 => HandLevel::FiveOfAKind,
+        2 => HandLevel::StraightFlush,
+        3 => HandLevel::Quads,
+        4 => HandLevel::Flush,
+        5 => HandLevel::FullHouse,
+        6 => HandLevel::Trips,
+        7 => HandLevel::Straight,
+        8 => HandLevel::TwoPair,
+        9 => HandLevel::Pair,
+        10 => HandLevel::NoPair,
+        _ => HandLevel::None,
+    }
+}
+
 #[inline]
 const fn ar_value_from_level(l: HandLevel) -> u32 {
     match l {
@@ -679,8 +729,8 @@ const fn ar_level_from_value(v: u32) -> HandLevel {
 #[inline]
 const fn tc_value_from_level(l: HandLevel) -> u32 {
     match l {
-        HandLevel::StraightFlush => 1,
-        HandLevel::Trips => 2,
+        HandLevel::Trips => 1,
+        HandLevel::StraightFlush => 2,
         HandLevel::Straight => 3,
         HandLevel::Flush => 4,
         HandLevel::Pair => 5,
@@ -692,8 +742,8 @@ const fn tc_value_from_level(l: HandLevel) -> u32 {
 #[inline]
 const fn tc_level_from_value(v: u32) -> HandLevel {
     match v {
-        1 => HandLevel::StraightFlush,
-        2 => HandLevel::Trips,
+        1 => HandLevel::Trips,
+        2 => HandLevel::StraightFlush,
         3 => HandLevel::Straight,
         4 => HandLevel::Flush,
         5 => HandLevel::Pair,
@@ -714,6 +764,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: true,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 2
@@ -726,6 +777,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 3
@@ -738,6 +790,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 4
@@ -750,6 +803,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 5
@@ -762,6 +816,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 6
@@ -774,6 +829,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 7
@@ -786,18 +842,20 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: true,
         pai_gow_wheel: true,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 8
         name: "stripped",
-        deck_type: DeckType::English,
+        deck_type: DeckType::Stripped,
         complete_hand: 5,
         low_aces: false,
         low_hands: false,
         straights_and_flushes: true,
-        high_wheel: true,
+        high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: true,
         spanish_gap: false,
     },
     ScaleInfo {     // 9
@@ -810,6 +868,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 10
@@ -822,6 +881,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: true,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 11
@@ -834,6 +894,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: false,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: false,
     },
     ScaleInfo {     // 12
@@ -846,6 +907,7 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: true,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
         spanish_gap: true,
     },
     ScaleInfo {     // 13
@@ -858,6 +920,20 @@ const SCALE_INFO_TABLE: [ScaleInfo; SCALE_MAX] = [
         high_wheel: true,
         pai_gow_wheel: false,
         low_broadway: false,
+        short_wheel: false,
+        spanish_gap: false,
+    },
+    ScaleInfo {     // 14
+        name: "short-deck",
+        deck_type: DeckType::Swiss,
+        complete_hand: 5,
+        low_aces: false,
+        low_hands: false,
+        straights_and_flushes: true,
+        high_wheel: false,
+        pai_gow_wheel: false,
+        low_broadway: false,
+        short_wheel: true,
         spanish_gap: false,
     },
 ];
@@ -894,6 +970,9 @@ fn scale_by_alias(alias: &str) -> Scale {
         "stripped" | "manila"
         => Scale::Stripped,
 
+        "short-deck" | "shortdeck" | "six-plus" | "6+"
+        => Scale::ShortDeck,
+
         "mexican"
         => Scale::Mexican,
 
@@ -972,6 +1051,10 @@ mod tests {
         assert_eq!(Scale::by_name("three-card"), Scale::ThreeCard);
         assert_eq!(Scale::from_u8(13), Scale::ThreeCard);
 
+        assert_eq!(Scale::by_name("short-deck"), Scale::ShortDeck);
+        assert_eq!(Scale::by_name("6+"), Scale::ShortDeck);
+        assert_eq!(Scale::from_u8(14), Scale::ShortDeck);
+
         Ok(())
     }
 }
@@ -165,10 +165,19 @@ impl HandDescription {
         self.scale.full_text(self)
     }
 
-    /// Print relevant part of hand
+    /// Print relevant part of hand, with any bug shown as the card it
+    /// plays as
     pub fn hand_to_string(&self) -> String {
         let mut h = self.hand;
         h.truncate(self.length as usize);
+
+        if let HandExtras::Bugs(b) = self.extras {
+            let mut bugs = b.iter().filter(|c| c.is_card() && ! c.is_joker());
+            for i in 0..h.len() {
+                if ! h[i].is_joker() { continue; }
+                if let Some(c) = bugs.next() { h[i] = *c; }
+            }
+        }
         h.to_string()
     }
 
@@ -221,15 +230,17 @@ fn get_ranks_from_value(h: &mut Hand, g: Scale, value: HandValue)
         r.push(Rank::from_u8(d as u8));
     }
 
-    if h.len() > 5 && (lvl == HandLevel::Flush || lvl == HandLevel::StraightFlush) {
-        let mut counts = [0; 4];
+    if h.len() > g.complete_hand() &&
+        (lvl == HandLevel::Flush || lvl == HandLevel::StraightFlush) {
+
+        // Find the suit that has all the ranks of the flush
         let mut flush_suit = Suit::None;
+        for s in 1..=4 {
+            let suit = Suit::from_u8(s);
+            if r.iter().all(|&rank|
+                h.contains(Card::from_rank_suit(rank, suit))) {
 
-        // This code may fail for 10-card hands with two flushes
-        for i in 0..h.len() {
-            counts[h[i].suit() as usize - 1] += 1;
-            if counts[h[i].suit() as usize - 1] > 4 {
-                flush_suit = h[i].suit();
+                flush_suit = suit;
                 break;
             }
         }
@@ -350,7 +361,7 @@ impl Eq for HandDescription {}
 
 impl PartialOrd for HandDescription {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.value.cmp(&other.value))
+        Some(self.cmp(other))
     }
 }
 
@@ -383,4 +394,17 @@ mod tests {
         has_traits::<HandDescription>();
         Ok(())
     }
+
+    #[test]
+    fn test_hand_to_string_bugs() -> Result<()> {
+        let hand = Hand::new(DeckType::OneJoker).init(hand!("Ks","Qs","Jk","Ts","9s"));
+        let mut d = HandDescriptionBuilder::new(&hand, Scale::HighHandBug)
+            .with_level(HandLevel::StraightFlush)
+            .with_value(1).complete()?;
+        assert_eq!(d.hand_to_string(), "KsQsJkTs9s");
+
+        d.add_bugs([JACK_OF_SPADES, Card::default()]);
+        assert_eq!(d.hand_to_string(), "KsQsJsTs9s");
+        Ok(())
+    }
 }
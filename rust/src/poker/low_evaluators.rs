@@ -0,0 +1,281 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Lowball_Evaluators) | Table-free evaluators for larger lowball hands
+//!
+//! Finding the best 5-card lowball hand out of 7 (or more) cards doesn't
+//! need to look at all 21 subsets. Since lower ranks are always better,
+//! the best hand can be built directly from bitmasks of the ranks that
+//! appear once, twice, three and four times, with a little extra work
+//! for the straights and flushes of deuce-to-seven and ace-to-six.
+//! Values are identical to those of [ojp_reference_evaluator] on the best
+//! 5-card subset.
+
+use crate::cards::*;
+use crate::poker::*;
+
+// Nibble-packed ranks, most significant first, as in ojp_default_hand_value
+fn pack(ranks: &[u32]) -> u32 {
+    ranks.iter().fold(0, |h, r| (h << 4) | r)
+}
+
+fn value(g: Scale, l: HandLevel, ranks: &[u32]) -> HandValue {
+    HAND_LEVEL_MULTIPLIER * g.value_from_level(l) + pack(ranks)
+}
+
+// Lowest rank in mask
+#[inline]
+fn low(m: u32) -> u32 {
+    m.trailing_zeros()
+}
+
+// Highest rank in mask
+#[inline]
+fn high(m: u32) -> u32 {
+    31 - m.leading_zeros()
+}
+
+// Mask without its lowest rank
+#[inline]
+fn drop_low(m: u32) -> u32 {
+    m & (m - 1)
+}
+
+// Lowest k ranks of mask, or None if there aren't that many
+fn lowest(mut m: u32, k: u32) -> Option<u32> {
+    let mut out = 0;
+    for _ in 0..k {
+        if 0 == m { return None; }
+        out |= m & m.wrapping_neg();
+        m = drop_low(m);
+    }
+    Some(out)
+}
+
+// Ranks of mask from highest to lowest
+fn descending(mut m: u32, out: &mut [u32]) {
+    for r in out.iter_mut() {
+        *r = high(m);
+        m &= ! (1 << *r);
+    }
+}
+
+// Five consecutive ranks, skipping the knight that isn't in these decks
+fn is_straight_mask(m: u32) -> bool {
+    let m = (m & 0x0FFF) | ((m >> 1) & ! 0x0FFF);
+    0b11111 == m >> low(m)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_lowball_evaluator) | Best 5-card lowball value of 5 or more cards
+///
+/// For ace-to-five, deuce-to-seven, and ace-to-six hands of at least 5
+/// cards, gives the same value as [ojp_best_of] with
+/// [ojp_reference_evaluator], but much faster.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_lowball_evaluator, ojp_best_of,
+///     ojp_reference_evaluator};
+///
+/// let h = Hand::new(DeckType::English)
+///     .init(hand!("7s","5s","4s","3s","2s","8h","Kd"));
+/// let v = ojp_lowball_evaluator(&h, Scale::DeuceToSeven);
+/// assert_eq!(v, ojp_best_of(&h, 5, Scale::DeuceToSeven,
+///     |h| ojp_reference_evaluator(h, Scale::DeuceToSeven)));
+/// assert_eq!(v, 0x185432);    // 8-5-4-3-2, avoiding the flush
+/// ```
+pub fn ojp_lowball_evaluator(hand: &Hand, g: Scale) -> HandValue {
+    debug_assert!(g.low_hands() && 5 == g.complete_hand());
+    debug_assert!(hand.len() >= 5);
+    debug_assert!(g.valid_hand(hand));
+
+    // Masks of ranks appearing at least 1, 2, 3, 4 times, and the suits
+    // of each rank.
+    let mut seen = [0u32; 4];
+    let mut suits = [0u8; 16];
+
+    for c in hand.iter() {
+        let r = c.rank() as u32;
+        let b = 1 << r;
+        seen[3] |= seen[2] & b;
+        seen[2] |= seen[1] & b;
+        seen[1] |= seen[0] & b;
+        seen[0] |= b;
+        suits[r as usize] |= 1 << c.suit() as u8;
+    }
+    let [ones, pairs, trips, quads] = seen;
+    let mut best = HAND_VALUE_WORST;
+    let mut r = [0u32; 5];
+
+    // No pair: distinct ranks in increasing numeric order of mask are
+    // also in increasing value order, so the first usable one is best.
+    // Otherwise, keep track of the best straight or flush.
+    let distinct = ones.count_ones();
+    if distinct >= 5 {
+        if ! g.straights_and_flushes() {
+            descending(lowest(ones, 5).expect("5 ranks"), &mut r);
+            return value(g, HandLevel::NoPair, &r);
+        }
+        let mut idx: u32 = 0b11111;
+        while idx < (1 << distinct) {
+            let m = pdep(idx, ones);
+            let mut suit_mask = 0;
+            let mut rest = m;
+            while 0 != rest {
+                suit_mask |= suits[low(rest) as usize];
+                rest = drop_low(rest);
+            }
+            let flush = 1 == suit_mask.count_ones();
+            let straight = is_straight_mask(m);
+
+            descending(m, &mut r);
+            let level = match (straight, flush) {
+                (false, false) => return value(g, HandLevel::NoPair, &r),
+                (true, false) => HandLevel::Straight,
+                (false, true) => HandLevel::Flush,
+                (true, true) => HandLevel::StraightFlush,
+            };
+            best = best.min(value(g, level, &r));
+
+            // Gosper's hack: next larger number with the same bit count
+            let c = idx & idx.wrapping_neg();
+            let n = idx + c;
+            idx = (((n ^ idx) >> 2) / c) | n;
+        }
+    }
+
+    // One pair, lowest pair with three lowest other ranks
+    if 0 != pairs {
+        let p = low(pairs);
+        if let Some(k) = lowest(ones & ! (1 << p), 3) {
+            descending(k, &mut r[2..]);
+            r[0] = p;
+            r[1] = p;
+            best = best.min(value(g, HandLevel::Pair, &r));
+        }
+    }
+    // Two pair, two lowest pairs with lowest other rank
+    if pairs.count_ones() >= 2 {
+        let lo = low(pairs);
+        let hi = low(drop_low(pairs));
+        let rest = ones & ! (1 << lo) & ! (1 << hi);
+        if 0 != rest {
+            r = [hi, hi, lo, lo, low(rest)];
+            best = best.min(value(g, HandLevel::TwoPair, &r));
+        }
+    }
+    if 0 != trips {
+        let t = low(trips);
+
+        // Trips with two lowest other ranks
+        if let Some(k) = lowest(ones & ! (1 << t), 2) {
+            descending(k, &mut r[3..]);
+            r[0] = t;
+            r[1] = t;
+            r[2] = t;
+            best = best.min(value(g, HandLevel::Trips, &r));
+        }
+        // Full house with lowest other pair
+        let p = pairs & ! (1 << t);
+        if 0 != p {
+            r = [t, t, t, low(p), low(p)];
+            best = best.min(value(g, HandLevel::FullHouse, &r));
+        }
+    }
+    // Quads with lowest other rank
+    if 0 != quads {
+        let q = low(quads);
+        let k = ones & ! (1 << q);
+        if 0 != k {
+            r = [q, q, q, q, low(k)];
+            best = best.min(value(g, HandLevel::Quads, &r));
+        }
+    }
+    debug_assert!(best != HAND_VALUE_WORST);
+    best
+}
+
+// Deposit the low bits of idx into the set bits of mask, lowest first
+fn pdep(mut idx: u32, mut mask: u32) -> u32 {
+    let mut out = 0;
+    while 0 != idx {
+        let b = mask & mask.wrapping_neg();
+        if 0 != idx & 1 { out |= b; }
+        mask ^= b;
+        idx >>= 1;
+    }
+    out
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_lowball_evaluator() -> Result<()> {
+        let expected = |h: &Hand, g: Scale, eval: HandEvaluator| {
+            if 5 == h.len() { eval(h) } else { ojp_best_of(h, 5, g, eval) }
+        };
+        let evaluators: [(Scale, HandEvaluator); 3] = [
+            (Scale::AceToFive, |h| ojp_reference_evaluator(h, Scale::AceToFive)),
+            (Scale::DeuceToSeven,
+                |h| ojp_reference_evaluator(h, Scale::DeuceToSeven)),
+            (Scale::AceToSix, |h| ojp_reference_evaluator(h, Scale::AceToSix)),
+        ];
+        for (g, eval) in evaluators {
+            let mut deck = Deck::new(g.deck_type());
+
+            for i in 0..6000 {
+                deck.refill_and_shuffle();
+                let n = 5 + i % 4;
+                let cards: Vec<Card> = deck.draw(n).collect();
+                let h = deck.new_hand().init(cards);
+                assert_eq!(ojp_lowball_evaluator(&h, g), expected(&h, g, eval),
+                    "{} {}", g.name(), h);
+            }
+            // Every 6- and 7-card hand from two suits of the lowest ranks,
+            // full of straights and flushes
+            let cards = g.deck_type().card_list();
+            let lowest = cards.iter().map(|c| c.rank()).min().unwrap();
+            let small: Vec<Card> = cards.iter()
+                .filter(|c| c.suit() >= Suit::Heart &&
+                    POKER_RANK_ORDER[c.rank() as usize] <
+                    POKER_RANK_ORDER[lowest as usize] + 7)
+                .copied().collect();
+            assert_eq!(small.len(), 14);
+            let small = deck.new_hand().init(small);
+            for k in [6, 7] {
+                for h in small.combinations(k) {
+                    assert_eq!(ojp_lowball_evaluator(&h, g),
+                        expected(&h, g, eval), "{} {}", g.name(), h);
+                }
+            }
+        }
+
+        // Straights and flushes that have to be avoided, or can't be
+        let deck = Deck::new(DeckType::English);
+        let g = Scale::DeuceToSeven;
+        for (text, level) in [
+            ("7s6s5s4s3s2s", HandLevel::Flush),
+            ("6s5s4s3s2s", HandLevel::StraightFlush),
+            ("8s7s6s5s4s", HandLevel::StraightFlush),
+            ("6d5s4s3s2s7d", HandLevel::NoPair),
+            ("6d5s4s3s2s6s", HandLevel::Pair),
+            ("8s7s6s5s4s9s", HandLevel::Flush),
+            ("8s7d6s5s4s9s", HandLevel::NoPair),
+            ("8s7d6s5s4s", HandLevel::Straight),
+            ("AsAdAhAc2s2d", HandLevel::FullHouse),
+            ("AsAdAhAcKs", HandLevel::Quads),
+            ("KsKdKhQcQs", HandLevel::FullHouse),
+            ("KsKdQhQcJs", HandLevel::TwoPair),
+        ] {
+            let h = deck.new_hand().init(card_parse(text));
+            let v = ojp_lowball_evaluator(&h, g);
+            assert_eq!(v, expected(&h, g, evaluators[1].1), "{}", text);
+            assert_eq!(HandDescription::from_value(&h, g, v).level, level,
+                "{}", text);
+        }
+        Ok(())
+    }
+}
@@ -9,9 +9,33 @@ pub use hand_value::*;
 pub mod reference_evaluators;
 pub use reference_evaluators::*;
 
+pub mod low_evaluators;
+pub use low_evaluators::*;
+
 pub mod bug;
 pub use bug::*;
 
+pub mod best_hand;
+pub use best_hand::*;
+
+pub mod split;
+pub use split::*;
+
+pub mod game_rules;
+pub use game_rules::*;
+
+pub mod range;
+pub use range::*;
+
+pub mod equity;
+pub use equity::*;
+
+pub mod batch;
+pub use batch::*;
+
+pub mod engine;
+pub use engine::*;
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/Game) | Poker games
 pub mod games;
 pub use games::*;
@@ -21,5 +45,6 @@ pub mod tables;
 #[cfg(any(feature = "high-hand-tables",
     feature = "ace-to-five-tables",
     feature = "deuce-to-seven-tables",
-    feature = "badugi-tables"))]
+    feature = "badugi-tables",
+    feature = "three-card-tables"))]
 pub use tables::*;
@@ -0,0 +1,623 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Range) | Ranges of starting hands
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, Result};
+use crate::cards::*;
+
+// Ranks used in range shorthand, low to high (no knights or low aces)
+const RANGE_RANKS: [Rank; 13] = [
+    Rank::Deuce, Rank::Trey, Rank::Four, Rank::Five, Rank::Six,
+    Rank::Seven, Rank::Eight, Rank::Nine, Rank::Ten, Rank::Jack,
+    Rank::Queen, Rank::King, Rank::Ace,
+];
+
+fn rank_index(r: Rank) -> usize {
+    RANGE_RANKS.iter().position(|&x| x == r).expect("range rank")
+}
+
+pub(crate) fn mask_of<I>(cards: I) -> u64
+where I: IntoIterator<Item = Card> {
+    cards.into_iter().collect::<CardSet>().0
+}
+
+fn hand_of(mask: u64) -> Hand {
+    Hand::new(DeckType::English).init(CardSet(mask).iter().rev())
+}
+
+// Suit requirement following the ranks of a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Suits {
+    Any,
+    Suited,
+    Offsuit,
+    DoubleSuited,
+    SingleSuited,
+}
+
+impl Suits {
+    fn from_suffix(s: &str) -> Option<Suits> {
+        match s {
+            "" => Some(Suits::Any),
+            "s" => Some(Suits::Suited),
+            "o" | "r" => Some(Suits::Offsuit),
+            "ds" => Some(Suits::DoubleSuited),
+            "ss" => Some(Suits::SingleSuited),
+            _ => None,
+        }
+    }
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            Suits::Any => "",
+            Suits::Suited => "s",
+            Suits::Offsuit => "o",
+            Suits::DoubleSuited => "ds",
+            Suits::SingleSuited => "ss",
+        }
+    }
+
+    fn matches(&self, mask: u64) -> bool {
+        let mut counts = [0u32; 4];
+        for (s, c) in counts.iter_mut().enumerate() {
+            *c = (mask & (0x1111_1111_1111_1111 << s)).count_ones();
+        }
+        let used = counts.iter().filter(|&&c| c > 0).count();
+        let twos = counts.iter().filter(|&&c| 2 == c).count();
+        let most = *counts.iter().max().unwrap();
+
+        match self {
+            Suits::Any => true,
+            Suits::Suited => 1 == used,
+            Suits::Offsuit => most <= 1,
+            Suits::DoubleSuited => 2 == twos && most <= 2,
+            Suits::SingleSuited => 1 == twos && most <= 2,
+        }
+    }
+}
+
+// Ranks (`None` for "*") and suit requirement, e.g. "AKs" or "AA**ds"
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern {
+    ranks: Vec<Rank>,
+    suits: Suits,
+}
+
+impl Pattern {
+    fn parse(text: &str) -> Result<Pattern> {
+        let split = text.find(|c: char| c.is_ascii_lowercase())
+            .unwrap_or(text.len());
+        let (rtext, stext) = text.split_at(split);
+
+        let Some(suits) = Suits::from_suffix(stext) else {
+            return Err(Error::ParseOther(format!("bad suits \"{}\" in {}",
+                stext, text)));
+        };
+        let mut ranks: Vec<Rank> = Vec::new();
+        for c in rtext.chars() {
+            if '*' == c {
+                ranks.push(Rank::None);
+                continue;
+            }
+            let r = Rank::from_char(c);
+            if ! RANGE_RANKS.contains(&r) {
+                return Err(Error::NotRank(c.to_string()));
+            }
+            ranks.push(r);
+        }
+        if ranks.len() < 2 {
+            return Err(Error::ParseOther(format!("too few cards in {}", text)));
+        }
+        ranks.sort_by(|a, b| b.cmp(a));
+        Ok(Pattern { ranks, suits })
+    }
+
+    fn is_pair(&self) -> bool {
+        2 == self.ranks.len() && self.ranks[0] == self.ranks[1]
+    }
+
+    fn is_two_card(&self) -> bool {
+        2 == self.ranks.len() && Rank::None != self.ranks[1]
+    }
+
+    fn with_ranks(&self, hi: Rank, lo: Rank) -> Pattern {
+        Pattern { ranks: vec![hi, lo], suits: self.suits }
+    }
+
+    // All the masks of concrete hands matching the pattern
+    fn expand(&self) -> Result<Vec<u64>> {
+        let mut masks: Vec<u64> = vec![0];
+
+        for &r in RANGE_RANKS.iter() {
+            let n = self.ranks.iter().filter(|&&x| x == r).count();
+            if 0 == n {
+                continue;
+            }
+            if n > 4 {
+                return Err(Error::BadHand(format!("more than four {}",
+                    r.plural())));
+            }
+            let all = Hand::new(DeckType::English).init([Suit::Club,
+                Suit::Diamond, Suit::Heart, Suit::Spade].iter()
+                .map(|&s| Card::from_rank_suit(r, s)));
+            masks = masks.iter().flat_map(|&m| all.combinations(n)
+                .map(move |h| m | mask_of(h.iter()))).collect();
+        }
+        let wild = self.ranks.iter().filter(|&&x| Rank::None == x).count();
+        if wild > 0 {
+            masks = masks.iter().flat_map(|&m| {
+                let mut deck = Deck::new(DeckType::English);
+                for c in hand_of(m).iter() {
+                    deck.remove_card(c);
+                }
+                deck.combinations(wild).map(move |h| m | mask_of(h.iter()))
+                    .collect::<Vec<u64>>()
+            }).collect();
+        }
+        masks.retain(|&m| self.suits.matches(m));
+        masks.sort_unstable();
+        masks.dedup();
+        Ok(masks)
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for r in self.ranks.iter() {
+            write!(f, "{}", if Rank::None == *r { '*' } else { r.to_char() })?;
+        }
+        write!(f, "{}", self.suits.suffix())
+    }
+}
+
+// Patterns for "TT+", "ATs+", "TT-77", and "A5s-A2s"
+fn pattern_run(text: &str) -> Result<Vec<Pattern>> {
+    let bad = || Error::ParseOther(format!("bad range {}", text));
+
+    if let Some(base) = text.strip_suffix('+') {
+        let p = Pattern::parse(base)?;
+        if ! p.is_two_card() {
+            return Err(bad());
+        }
+        let (hi, lo) = (rank_index(p.ranks[0]), rank_index(p.ranks[1]));
+
+        if p.is_pair() {
+            return Ok(RANGE_RANKS[hi..].iter()
+                .map(|&r| p.with_ranks(r, r)).collect());
+        }
+        return Ok(RANGE_RANKS[lo..hi].iter()
+            .map(|&r| p.with_ranks(p.ranks[0], r)).collect());
+    }
+    let Some((a, b)) = text.split_once('-') else {
+        return Ok(vec![Pattern::parse(text)?]);
+    };
+    let (pa, pb) = (Pattern::parse(a)?, Pattern::parse(b)?);
+    if ! pa.is_two_card() || ! pb.is_two_card() || pa.suits != pb.suits {
+        return Err(bad());
+    }
+    if pa.is_pair() && pb.is_pair() {
+        let (x, y) = (rank_index(pa.ranks[0]), rank_index(pb.ranks[0]));
+        return Ok(RANGE_RANKS[x.min(y)..=x.max(y)].iter()
+            .map(|&r| pa.with_ranks(r, r)).collect());
+    }
+    if pa.is_pair() || pb.is_pair() || pa.ranks[0] != pb.ranks[0] {
+        return Err(bad());
+    }
+    let (x, y) = (rank_index(pa.ranks[1]), rank_index(pb.ranks[1]));
+    Ok(RANGE_RANKS[x.min(y)..=x.max(y)].iter()
+        .map(|&r| pa.with_ranks(pa.ranks[0], r)).collect())
+}
+
+// Concrete cards like "AsKd"
+fn concrete(text: &str) -> Result<u64> {
+    let cards: Vec<Card> = card_parse(text).collect();
+
+    if 2 * cards.len() != text.len() || cards.len() < 2 {
+        return Err(Error::ParseOther(format!("bad cards {}", text)));
+    }
+    let mut mask: u64 = 0;
+    for c in cards.iter() {
+        if ! RANGE_RANKS.contains(&c.rank()) {
+            return Err(Error::InvalidCard(*c, DeckType::English.name().into()));
+        }
+        if 0 != mask & (1 << c.0) {
+            return Err(Error::DuplicateCard(*c));
+        }
+        mask |= 1 << c.0;
+    }
+    Ok(mask)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/Range) | Weighted set of starting hands
+///
+/// A set of concrete hands from the English deck, each with a weight in
+/// (0, 1]. Ranges are parsed from comma-separated shorthand:
+///
+/// - `AKs`, `AKo`, `AK`, `TT`: suited, offsuit, either, or a pair
+/// - `TT+`, `ATs+`: pairs up to aces, kickers up to just below the top card
+/// - `TT-77`, `A5s-A2s`: runs of pairs or kickers
+/// - `AsKd`, `AsAdKcQh`: concrete cards
+/// - `AA**`, `AA**ds`, `KQJT**ss`, `****r`: patterns for larger hands, where
+///   `*` is any card and the suffix is one of `s` (suited), `o` or `r`
+///   (no two cards suited), `ds` (double-suited), `ss` (single-suited)
+/// - any of the above with `:0.5` for a weight
+///
+/// Later entries replace the weights of earlier ones. Ranges print back
+/// in the same shorthand, with patterns for larger hands printed as they
+/// were parsed as long as all their hands are still in the range.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::Range;
+///
+/// let r = Range::parse("AKs, TT+, A5s-A2s, KQo").unwrap();
+/// assert_eq!(r.len(), 4 + 5 * 6 + 4 * 4 + 12);
+/// assert_eq!(r.to_string(), "TT+, AKs, A5s-A2s, KQo");
+///
+/// let omaha = Range::parse("AA**ds").unwrap();
+/// let h = Hand::new(DeckType::English).init(hand!("As","Ad","7s","6d"));
+/// assert!(omaha.contains(&h));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Range {
+    combos: BTreeMap<u64, f64>,
+    // Patterns other than two-card classes, with weights, as parsed
+    patterns: Vec<(Pattern, f64)>,
+}
+
+impl Range {
+    /// New empty range
+    pub fn new() -> Range {
+        Range::default()
+    }
+
+    /// Parse range from shorthand text
+    pub fn parse(text: &str) -> Result<Range> {
+        let mut r = Range::new();
+
+        for item in text.split(',').map(|s| s.trim()) {
+            if item.is_empty() {
+                continue;
+            }
+            let (body, weight) = match item.split_once(':') {
+                Some((b, w)) => (b.trim(), w.trim().parse::<f64>().map_err(|_|
+                    Error::ParseOther(format!("bad weight in {}", item)))?),
+                None => (item, 1.0),
+            };
+            if !(weight > 0.0 && weight <= 1.0) {
+                return Err(Error::ParseOther(format!("bad weight in {}", item)));
+            }
+            let concrete_cards = body.chars().skip(1).step_by(2)
+                .all(|c| c.is_ascii_lowercase()) && body.len() > 3;
+
+            if concrete_cards {
+                r.combos.insert(concrete(body)?, weight);
+                continue;
+            }
+            let mut found = false;
+            for p in pattern_run(body)? {
+                for m in p.expand()? {
+                    r.combos.insert(m, weight);
+                    found = true;
+                }
+                if ! p.is_two_card() {
+                    r.patterns.push((p, weight));
+                }
+            }
+            if ! found {
+                return Err(Error::ParseOther(format!("no hands match {}", item)));
+            }
+        }
+        Ok(r)
+    }
+
+    /// Number of concrete hands in the range
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Is the range empty?
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Sum of the weights of all hands
+    pub fn total_weight(&self) -> f64 {
+        self.combos.values().sum()
+    }
+
+    /// Add a hand to the range, or change its weight
+    pub fn insert(&mut self, h: &Hand, weight: f64) {
+        debug_assert!(weight > 0.0 && weight <= 1.0);
+        self.combos.insert(mask_of(h.iter()), weight);
+    }
+
+    /// Remove a hand from the range
+    pub fn remove(&mut self, h: &Hand) -> bool {
+        self.combos.remove(&mask_of(h.iter())).is_some()
+    }
+
+    /// Does the range contain this hand (in any order)?
+    pub fn contains(&self, h: &Hand) -> bool {
+        self.combos.contains_key(&mask_of(h.iter()))
+    }
+
+    /// Weight of this hand, 0 if not in the range
+    pub fn weight(&self, h: &Hand) -> f64 {
+        self.combos.get(&mask_of(h.iter())).copied().unwrap_or(0.0)
+    }
+
+    /// Iterate over concrete hands and their weights
+    /// ```rust
+    /// use onejoker::poker::Range;
+    ///
+    /// let r = Range::parse("AKs:0.5").unwrap();
+    /// let v: Vec<String> = r.combos().map(|(h, _)| h.to_string()).collect();
+    /// assert_eq!(v, ["AcKc", "AdKd", "AhKh", "AsKs"]);
+    /// assert_eq!(r.total_weight(), 2.0);
+    /// ```
+    pub fn combos(&self) -> impl Iterator<Item = (Hand, f64)> + '_ {
+        self.combos.iter().map(|(&m, &w)| (hand_of(m), w))
+    }
+
+    /// Remove hands containing any of the dead cards
+    /// ```rust
+    /// use onejoker::prelude::*;
+    /// use onejoker::poker::Range;
+    ///
+    /// let mut r = Range::parse("AA, AKs").unwrap();
+    /// r.remove_dead(&Hand::new(DeckType::English).init(hand!("As")));
+    /// assert_eq!(r.len(), 3 + 3);
+    /// ```
+    pub fn remove_dead(&mut self, dead: &Hand) {
+        let d = mask_of(dead.iter());
+        self.combos.retain(|&m, _| 0 == m & d);
+    }
+
+    /// Hands in either range, with the larger weight
+    pub fn union(&self, other: &Range) -> Range {
+        let mut combos = self.combos.clone();
+        for (&m, &w) in other.combos.iter() {
+            let e = combos.entry(m).or_insert(w);
+            *e = e.max(w);
+        }
+        Range { combos, patterns: self.both_patterns(other) }
+    }
+
+    /// Hands in both ranges, with the smaller weight
+    pub fn intersection(&self, other: &Range) -> Range {
+        Range {
+            combos: self.combos.iter().filter_map(|(&m, &w)|
+                other.combos.get(&m).map(|&w2| (m, w.min(w2)))).collect(),
+            patterns: self.both_patterns(other),
+        }
+    }
+
+    /// Hands in this range but not the other
+    /// ```rust
+    /// use onejoker::poker::Range;
+    ///
+    /// let a = Range::parse("22+").unwrap();
+    /// let b = Range::parse("TT-55").unwrap();
+    /// assert_eq!(a.difference(&b).to_string(), "JJ+, 44-22");
+    /// assert_eq!(a.intersection(&b), b);
+    /// assert_eq!(b.union(&a.difference(&b)), a);
+    /// ```
+    pub fn difference(&self, other: &Range) -> Range {
+        Range {
+            combos: self.combos.iter().filter(|(m, _)|
+                ! other.combos.contains_key(m))
+                .map(|(&m, &w)| (m, w)).collect(),
+            patterns: self.patterns.clone(),
+        }
+    }
+
+    // Patterns of both ranges, any of which may still print
+    fn both_patterns(&self, other: &Range) -> Vec<(Pattern, f64)> {
+        self.patterns.iter().chain(other.patterns.iter()).cloned().collect()
+    }
+}
+
+// Same hands with the same weights, however they were written
+impl PartialEq for Range {
+    fn eq(&self, other: &Self) -> bool {
+        self.combos == other.combos
+    }
+}
+
+impl From<&Hand> for Range {
+    fn from(h: &Hand) -> Self {
+        let mut r = Range::new();
+        r.insert(h, 1.0);
+        r
+    }
+}
+
+impl std::str::FromStr for Range {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Range::parse(s)
+    }
+}
+
+// Canonical text for a run of complete two-card classes, all of which
+// share the same high card (or are pairs) and weight
+fn run_text(p: &Pattern, top: Rank, bottom: Rank, ceiling: Rank) -> String {
+    let hi = if p.is_pair() { top } else { p.ranks[0] };
+    let first = p.with_ranks(hi, top);
+    let last = p.with_ranks(if p.is_pair() { bottom } else { hi }, bottom);
+
+    if top == bottom {
+        first.to_string()
+    } else if top == ceiling {
+        format!("{}+", last)
+    } else {
+        format!("{}-{}", first, last)
+    }
+}
+
+impl std::fmt::Display for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut items: Vec<String> = Vec::new();
+        let mut leftover: BTreeMap<u64, f64> = self.combos.clone();
+
+        // Weight of a two-card class if all its hands are present and equal
+        let full = |p: &Pattern, left: &BTreeMap<u64, f64>| -> Option<f64> {
+            let masks = p.expand().ok()?;
+            let w = *left.get(&masks[0])?;
+            masks.iter().all(|m| left.get(m) == Some(&w)).then_some(w)
+        };
+        let weighted = |s: String, w: f64| {
+            if 1.0 == w { s } else { format!("{}:{}", s, w) }
+        };
+
+        for (p, w) in self.patterns.iter() {
+            let masks = p.expand().unwrap_or_default();
+
+            if masks.iter().all(|m| leftover.get(m) == Some(w)) {
+                items.push(weighted(p.to_string(), *w));
+                for m in masks.iter() {
+                    leftover.remove(m);
+                }
+            }
+        }
+        let mut groups: Vec<(Pattern, Vec<Rank>, Rank)> = Vec::new();
+        let pair = Pattern { ranks: vec![Rank::Ace, Rank::Ace], suits: Suits::Any };
+        groups.push((pair, RANGE_RANKS.to_vec(), Rank::Ace));
+        for suits in [Suits::Any, Suits::Suited, Suits::Offsuit] {
+            for i in (1..RANGE_RANKS.len()).rev() {
+                let p = Pattern { ranks: vec![RANGE_RANKS[i], Rank::None], suits };
+                groups.push((p, RANGE_RANKS[..i].to_vec(), RANGE_RANKS[i - 1]));
+            }
+        }
+        for (p, lows, ceiling) in groups {
+            let class = |r: Rank| if p.is_pair() {
+                p.with_ranks(r, r)
+            } else {
+                p.with_ranks(p.ranks[0], r)
+            };
+            let mut run: Option<(Rank, Rank, f64)> = None;
+
+            for &r in lows.iter().rev() {
+                let w = full(&class(r), &leftover);
+
+                match (run, w) {
+                    (Some((top, _, rw)), Some(w)) if rw == w => {
+                        run = Some((top, r, rw));
+                    },
+                    _ => {
+                        if let Some((top, bottom, rw)) = run.take() {
+                            items.push(weighted(run_text(&p, top, bottom,
+                                ceiling), rw));
+                        }
+                        if let Some(w) = w {
+                            run = Some((r, r, w));
+                        }
+                    },
+                }
+                if w.is_some() {
+                    for m in class(r).expand().unwrap_or_default() {
+                        leftover.remove(&m);
+                    }
+                }
+            }
+            if let Some((top, bottom, rw)) = run {
+                items.push(weighted(run_text(&p, top, bottom, ceiling), rw));
+            }
+        }
+
+        for (&m, &w) in leftover.iter().rev() {
+            items.push(weighted(hand_of(m).to_string(), w));
+        }
+        write!(f, "{}", items.join(", "))
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() -> Result<()> {
+        let deck = Deck::new(DeckType::English);
+
+        assert_eq!(Range::parse("AA")?.len(), 6);
+        assert_eq!(Range::parse("AKs")?.len(), 4);
+        assert_eq!(Range::parse("AKo")?.len(), 12);
+        assert_eq!(Range::parse("AK")?.len(), 16);
+        assert_eq!(Range::parse("TT+")?.len(), 30);
+        assert_eq!(Range::parse("ATs+")?.len(), 16);
+        assert_eq!(Range::parse("A2s-A5s")?.len(), 16);
+        assert_eq!(Range::parse("77-TT")?, Range::parse("TT-77")?);
+        assert_eq!(Range::parse("A*")?.len(), 4 * 48 + 6);
+        assert_eq!(Range::parse("**")?.len(), 1326);
+        assert_eq!(Range::parse("AsKd, KdAs")?.len(), 1);
+        assert_eq!(Range::parse("")?.len(), 0);
+
+        // Omaha
+        assert_eq!(Range::parse("AsAdKcKh")?.len(), 1);
+        assert_eq!(Range::parse("AA**")?.len(), 6 * 1128 + 4 * 48 + 1);
+        assert_eq!(Range::parse("AAKK")?.len(), 36);
+        assert_eq!(Range::parse("AAKKds")?.len(), 6);
+        assert_eq!(Range::parse("AKQJr")?.len(), 24);
+        assert_eq!(Range::parse("AKQJss")?.len(), 6 * 4 * 3 * 2);
+        let h = deck.new_hand().init(hand!("As","Ad","Ac","6d"));
+        assert!(Range::parse("AA**")?.contains(&h));
+        assert!(Range::parse("AA**ss")?.contains(&h));
+        assert!(! Range::parse("AA**ds")?.contains(&h));
+
+        for bad in ["AKx", "A", "AKs+:2", "AK:0", "AAAAA", "AsAs", "AA-KQ",
+            "A*+", "AsKdQ", "TTs", "AKds", "JkAs", "XK"] {
+            assert!(Range::parse(bad).is_err(), "{}", bad);
+        }
+
+        // Weights, and canonical text
+        let r = Range::parse("KK+, QQ:0.5, AKs, AQs:0.25, A9s-A7s, 76o, AsJd")?;
+        assert_eq!(r.weight(&deck.new_hand().init(hand!("Qc","Qh"))), 0.5);
+        assert_eq!(r.weight(&deck.new_hand().init(hand!("Jc","Qh"))), 0.0);
+        assert_eq!(r.to_string(),
+            "KK+, QQ:0.5, AKs, AQs:0.25, A9s-A7s, 76o, AsJd");
+        assert_eq!(Range::parse(&r.to_string())?, r);
+
+        let mut r2 = r.clone();
+        r2.remove_dead(&deck.new_hand().init(hand!("Kc","2d")));
+        assert_eq!(r.len() - r2.len(), 3 + 1);
+        assert_eq!(r2.to_string(), "AA, QQ:0.5, AQs:0.25, A9s-A7s, 76o, \
+            AsKs, AsJd, AhKh, AdKd, KsKh, KsKd, KhKd");
+        assert_eq!(Range::parse(&r2.to_string())?, r2);
+
+        // Set operations
+        let a = Range::parse("QQ+, AK")?;
+        let b = Range::parse("KK+:0.5, AKs, KQs")?;
+        assert_eq!(a.union(&b).to_string(), "QQ+, AK, KQs");
+        assert_eq!(a.intersection(&b).to_string(), "KK+:0.5, AKs");
+        assert_eq!(a.difference(&b).to_string(), "QQ, AKo");
+        assert!(a.intersection(&Range::new()).is_empty());
+
+        // Round trips through canonical text
+        for (text, canonical) in [
+            ("AK", "AK"),
+            ("AKs, AKo", "AK"),
+            ("AK, AQ, AJ, KQ, KJs, 22-44, JTs:0.5", "44-22, AJ+, KQ, KJs, JTs:0.5"),
+            ("A9-A7, 65o:0.25", "A9-A7, 65o:0.25"),
+            ("AA**ds", "AA**ds"),
+            ("AA**, KKQQ:0.5, AsKsQdJd", "AA**, KKQQ:0.5, AsKsQdJd"),
+            ("A*, KK", "A*, KK"),
+            ("****r:0.5", "****o:0.5"),
+        ] {
+            let r = Range::parse(text)?;
+            assert_eq!(r.to_string(), canonical);
+            assert_eq!(r.to_string().parse::<Range>()?, r);
+        }
+        // Pattern no longer whole prints as what's left of it
+        let mut o = Range::parse("AAKKds")?;
+        o.remove_dead(&deck.new_hand().init(hand!("Ks")));
+        assert_eq!(o.to_string(), "AhAdKhKd, AhAcKhKc, AdAcKdKc");
+        assert_eq!(o.to_string().parse::<Range>()?, o);
+        Ok(())
+    }
+}
@@ -78,12 +78,12 @@ fn is_flush(h: &Hand, g: Scale) -> bool {
 }
 
 /// Work around knight gap
-const POKER_RANK_ORDER: [i8; 16] = [
+pub(crate) const POKER_RANK_ORDER: [i8; 16] = [
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, -1, 12, 13, 14
 ];
 
 /// Mexican poker uses Spanish deck with no 8/9/10, and Q for C.
-const MEXICAN_RANK_ORDER: [i8; 16] = [
+pub(crate) const MEXICAN_RANK_ORDER: [i8; 16] = [
     0, 1, 2, 3, 4, 5, 6, 7, -1, -1, -1, 8, -1, 9, 10, 11
 ];
 
@@ -126,6 +126,30 @@ fn is_straight(h: &mut Hand, g: Scale) -> bool {
             h[0] = tc;
             return true;
         }
+        if g.short_wheel() &&
+            h[0].rank() == Rank::Ace &&
+            h[4].rank() == lowest_rank(h.deck_type()) &&
+            is_sequence(&h[1..]) {
+
+            let tc = h[0];
+            h[0] = h[1];
+            h[1] = h[2];
+            h[2] = h[3];
+            h[3] = h[4];
+            h[4] = tc;
+            return true;
+        }
+    }
+    if 3 == g.complete_hand() && g.high_wheel() &&
+        h[0].rank() == Rank::Ace &&
+        h[1].rank() == Rank::Trey &&
+        h[2].rank() == Rank::Deuce {
+
+        let tc = h[0];
+        h[0] = h[1];
+        h[1] = h[2];
+        h[2] = tc;
+        return true;
     }
     if g.spanish_gap() {
         for i in 1..h.len() {
@@ -135,18 +159,31 @@ fn is_straight(h: &mut Hand, g: Scale) -> bool {
                 return false;
             }
         }
-    } else {
-        for i in 1..h.len() {
-            if POKER_RANK_ORDER[h[i].rank() as usize] + 1 !=
-                POKER_RANK_ORDER[h[i - 1].rank() as usize] {
+        return true;
+    }
+    is_sequence(&h[..])
+}
 
-                return false;
-            }
+// Cards (sorted descending) are consecutive ranks
+fn is_sequence(cards: &[Card]) -> bool {
+    for i in 1..cards.len() {
+        if POKER_RANK_ORDER[cards[i].rank() as usize] + 1 !=
+            POKER_RANK_ORDER[cards[i - 1].rank() as usize] {
+
+            return false;
         }
     }
     true
 }
 
+// Lowest rank of a (possibly stripped) deck, under which a short wheel
+// plays the ace, e.g. the 6 in A-6-7-8-9.
+fn lowest_rank(dt: DeckType) -> Rank {
+    dt.card_list().iter().rev()
+        .find(|c| ! c.is_joker() && ! c.is_ace())
+        .map_or(Rank::Deuce, |c| c.rank())
+}
+
 fn is_quads(h: &mut Hand) -> bool {
     if h.len() < 4 {
         return false;
@@ -0,0 +1,227 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Split) | High-low split games
+
+use crate::error::Result;
+use crate::cards::*;
+use crate::poker::*;
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/SplitRules) | Rules for a high-low split game
+///
+/// Scales for each half of the pot, the highest card a low hand may have
+/// to qualify (`None` for no qualifier), and how hole cards must play.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{SplitRules, HoleUsage};
+///
+/// let o8 = SplitRules::OMAHA_8;
+/// assert_eq!(o8.qualifier, Some(Rank::Eight));
+/// let stud9 = SplitRules::STUD_8.qualifier(Some(Rank::Nine));
+/// assert_eq!(stud9.usage, HoleUsage::Any);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SplitRules {
+    /// Scale for the high half
+    pub high: Scale,
+    /// Scale for the low half
+    pub low: Scale,
+    /// Highest card allowed in a qualifying low, or `None` for any low
+    pub qualifier: Option<Rank>,
+    /// How many hole cards must play, for both halves
+    pub usage: HoleUsage,
+}
+
+impl SplitRules {
+    /// Omaha high-low, eight or better
+    pub const OMAHA_8: SplitRules = SplitRules {
+        high: Scale::HighHand, low: Scale::AceToFive,
+        qualifier: Some(Rank::Eight), usage: HoleUsage::OMAHA,
+    };
+    /// Seven-card stud high-low, eight or better
+    pub const STUD_8: SplitRules = SplitRules {
+        high: Scale::HighHand, low: Scale::AceToFive,
+        qualifier: Some(Rank::Eight), usage: HoleUsage::Any,
+    };
+
+    /// New split game with ace-to-five low and no qualifier
+    pub const fn new(high: Scale, usage: HoleUsage) -> Self {
+        SplitRules { high, low: Scale::AceToFive, qualifier: None, usage }
+    }
+
+    /// Set scale for the low half
+    pub const fn low(mut self, low: Scale) -> Self {
+        self.low = low;
+        self
+    }
+
+    /// Set low qualifier, e.g. `Some(Rank::Nine)` for nine-or-better
+    pub const fn qualifier(mut self, q: Option<Rank>) -> Self {
+        self.qualifier = q;
+        self
+    }
+
+    /// Does the described low hand qualify?
+    pub fn qualifies(&self, d: &HandDescription) -> bool {
+        let Some(q) = self.qualifier else {
+            return true;
+        };
+        HandLevel::NoPair == d.level &&
+            d.hand.len() >= self.low.complete_hand() &&
+            d.hand[0].rank() <= q
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/SplitDescription) | Both halves of a split hand
+#[derive(Debug, Clone, Copy)]
+pub struct SplitDescription {
+    /// Best high hand
+    pub high: HandDescription,
+    /// Best low hand, if it qualifies
+    pub low: Option<HandDescription>,
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_split_description) | Evaluate both halves of a split hand
+///
+/// Finds the best high hand and the best low hand independently, each
+/// under the usage rule, and drops the low if it doesn't qualify.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_split_description, SplitRules};
+///
+/// let hole = Hand::new(DeckType::English).init(hand!("As","2d","Kc","Kd"));
+/// let board = Hand::new(DeckType::English).init(hand!("Ks","7d","4h","3c","Qd"));
+/// let d = ojp_split_description(&hole, &board, SplitRules::OMAHA_8).unwrap();
+/// assert_eq!(d.high.full_text(), "three kings, queen, seven");
+/// assert_eq!(d.low.unwrap().full_text(), "seven, four, trey, deuce, ace");
+/// ```
+pub fn ojp_split_description(hole: &Hand, board: &Hand, rules: SplitRules)
+-> Result<SplitDescription> {
+    let (hv, hh) = ojp_best_hand(hole, board, rules.high, rules.usage)?;
+    let (lv, lh) = ojp_best_hand(hole, board, rules.low, rules.usage)?;
+
+    let high = rules.high.description(&hh, hv);
+    let low = rules.low.description(&lh, lv);
+
+    Ok(SplitDescription {
+        high,
+        low: if rules.qualifies(&low) { Some(low) } else { None },
+    })
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_split_shares) | Pot shares for a split showdown
+///
+/// Fraction of the pot won by each player. The high half goes to the best
+/// high hands and the low half to the best qualifying lows, each split
+/// evenly among ties; with no qualifying low the high hands take it all.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::{ojp_split_description, ojp_split_shares, SplitRules};
+///
+/// let board = Hand::new(DeckType::English).init(hand!("Ks","7d","4h","3c","Qd"));
+/// let p1 = Hand::new(DeckType::English).init(hand!("As","2d","Kc","Kd"));
+/// let p2 = Hand::new(DeckType::English).init(hand!("Ah","2h","Jc","Td"));
+/// let p3 = Hand::new(DeckType::English).init(hand!("Qs","Qh","9s","9c"));
+///
+/// let d: Vec<_> = [p1, p2, p3].iter().map(|p|
+///     ojp_split_description(p, &board, SplitRules::OMAHA_8).unwrap()).collect();
+/// assert_eq!(ojp_split_shares(&d), vec![0.75, 0.25, 0.0]);   // quartered
+/// ```
+pub fn ojp_split_shares(players: &[SplitDescription]) -> Vec<f64> {
+    let mut shares = vec![0.0; players.len()];
+
+    let high: Vec<HandValue> = players.iter().map(|p| p.high.value).collect();
+    let low: Vec<Option<HandValue>> = players.iter()
+        .map(|p| p.low.map(|d| d.value)).collect();
+
+    let low_pot = if low.iter().any(|v| v.is_some()) { 0.5 } else { 0.0 };
+    award(&mut shares, &high, 1.0 - low_pot);
+
+    if low_pot > 0.0 {
+        let low: Vec<HandValue> = low.iter()
+            .map(|v| v.unwrap_or(HAND_VALUE_WORST)).collect();
+        award(&mut shares, &low, low_pot);
+    }
+    shares
+}
+
+// Split the given pot evenly among the players with the best value
+fn award(shares: &mut [f64], values: &[HandValue], pot: f64) {
+    let Some(&best) = values.iter().min() else {
+        return;
+    };
+    let winners = values.iter().filter(|&&v| v == best).count();
+
+    for (i, &v) in values.iter().enumerate() {
+        if v == best {
+            shares[i] += pot / winners as f64;
+        }
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() -> Result<()> {
+        let deck = Deck::new(DeckType::English);
+        let board = deck.new_hand().init(hand!("8s","7d","4h","Jc","Qd"));
+
+        // Omaha: only two of the hole cards count toward the low
+        let p1 = deck.new_hand().init(hand!("As","2d","3c","Kd"));
+        let d1 = ojp_split_description(&p1, &board, SplitRules::OMAHA_8)?;
+        assert_eq!(d1.low.unwrap().full_text(), "eight, seven, four, deuce, ace");
+        assert_eq!(d1.low.unwrap().hand.len(), 5);
+
+        let p2 = deck.new_hand().init(hand!("Ah","9h","9c","Kh"));
+        let d2 = ojp_split_description(&p2, &board, SplitRules::OMAHA_8)?;
+        assert!(d2.low.is_none());
+        let d2 = ojp_split_description(&p2, &board,
+            SplitRules::OMAHA_8.qualifier(Some(Rank::Nine)))?;
+        assert_eq!(d2.low.unwrap().hand[0].rank(), Rank::Nine);
+        let d2 = ojp_split_description(&p2, &board,
+            SplitRules::OMAHA_8.qualifier(None))?;
+        assert_eq!(d2.low.unwrap().level, HandLevel::NoPair);
+
+        // High and low split, or scooped when no low qualifies
+        let p3 = deck.new_hand().init(hand!("Ts","9s","Qh","Qs"));
+        let d3 = ojp_split_description(&p3, &board, SplitRules::OMAHA_8)?;
+        assert_eq!(d3.high.level, HandLevel::Straight);
+        assert!(d3.low.is_none());
+        assert_eq!(ojp_split_shares(&[d1, d3]), vec![0.5, 0.5]);
+        let d2 = ojp_split_description(&p2, &board, SplitRules::OMAHA_8)?;
+        assert_eq!(ojp_split_shares(&[d2, d3]), vec![0.0, 1.0]);
+
+        let p4 = deck.new_hand().init(hand!("2c","3d","Td","9d"));
+        let d4 = ojp_split_description(&p4, &board, SplitRules::OMAHA_8)?;
+        assert_eq!(d4.high.level, HandLevel::Straight);
+        assert_eq!(ojp_split_shares(&[d4, d2]), vec![1.0, 0.0]);
+
+        // Split high, quartered low
+        let p5 = deck.new_hand().init(hand!("Ad","2s","Tc","9c"));
+        let d5 = ojp_split_description(&p5, &board, SplitRules::OMAHA_8)?;
+        assert_eq!(ojp_split_shares(&[d4, d5, d1]),
+            vec![0.25, 0.5, 0.25]);
+
+        // Stud: any five of seven, no board
+        let empty = deck.new_hand();
+        let p6 = deck.new_hand().init(hand!("As","3s","5s","7s","Ks","9h","2d"));
+        let d6 = ojp_split_description(&p6, &empty, SplitRules::STUD_8)?;
+        assert_eq!(d6.high.level, HandLevel::Flush);
+        assert_eq!(d6.low.unwrap().full_text(), "seven, five, trey, deuce, ace");
+
+        let p7 = deck.new_hand().init(hand!("4s","4d","5c","7c","7h","6d","Kd"));
+        let d7 = ojp_split_description(&p7, &empty, SplitRules::STUD_8)?;
+        assert_eq!(d7.high.level, HandLevel::TwoPair);
+        assert!(d7.low.is_none());
+        assert_eq!(ojp_split_shares(&[d6, d7]), vec![1.0, 0.0]);
+
+        let p8 = deck.new_hand().init(hand!("Ac","2c","3h","6h","Kc","Kh","7d"));
+        let d8 = ojp_split_description(&p8, &empty, SplitRules::STUD_8)?;
+        assert_eq!(d8.low.unwrap().full_text(), "seven, six, trey, deuce, ace");
+        assert_eq!(ojp_split_shares(&[d6, d8]), vec![1.0, 0.0]);
+        Ok(())
+    }
+}
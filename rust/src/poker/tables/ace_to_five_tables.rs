@@ -1,20 +1,17 @@
 //! [wiki](https://github.com/lcrocker/ojpoker/wiki/Ace_To_Five_Tables) | Ace-to-five lookup tables
 
-use std::sync::LazyLock;
-use crate::poker::tables::ojp_load_binary_table;
+use crate::poker::tables::BinaryTable;
 
 const MP5_SIZE: usize = 371293; // 13^5
 const MP7_SIZE: usize = 6188 * 13 * 13;
 
 /// Map from perfect hash to equivalence class
-pub static OJP_A5_TABLE_1: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_a5_table_1.bin.gz", MP5_SIZE)
-});
+pub static OJP_A5_TABLE_1: BinaryTable =
+    BinaryTable::new("ojp_a5_table_1.bin.gz", MP5_SIZE);
 
 /// Map from equivalence class + 2 cards to new equivalence class
-pub static OJP_A5_TABLE_3: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_a5_table_3.bin.gz", MP7_SIZE)
-});
+pub static OJP_A5_TABLE_3: BinaryTable =
+    BinaryTable::new("ojp_a5_table_3.bin.gz", MP7_SIZE);
 
 /// Map from equivalence class to hand level and card ranks
 pub static OJP_A5_TABLE_2: [u32; 6189] = [
@@ -1,23 +1,18 @@
 //! [wiki](https://github.com/lcrocker/ojpoker/wiki/Badugi_Tables) | Badugi lookup tables
 
-use std::sync::LazyLock;
-use crate::poker::tables::ojp_load_binary_table;
+use crate::poker::tables::BinaryTable;
 
 /// Map from perfect hash to equivalence class
-pub static OJP_BG_TABLE_1: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_bg_table_1.bin.gz", 270726)
-});
+pub static OJP_BG_TABLE_1: BinaryTable =
+    BinaryTable::new("ojp_bg_table_1.bin.gz", 270726);
 
 /// Alias for BG table: Using high-ace deck and hash function yields
 /// identical table, so no reason to store two copies
-pub static OJP_BC_TABLE_1: LazyLock<&'static Vec<u16>> = LazyLock::new(|| {
-    &*OJP_BG_TABLE_1
-});
+pub static OJP_BC_TABLE_1: &BinaryTable = &OJP_BG_TABLE_1;
 
 /// Map from perfect hash to equivalence class
-pub static OJP_BG_TABLE_3: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_bg_table_3.bin.gz", 133784561)
-});
+pub static OJP_BG_TABLE_3: BinaryTable =
+    BinaryTable::new("ojp_bg_table_3.bin.gz", 133784561);
 
 /// Map from equivalence class to hand level and card ranks
 pub const OJP_BG_TABLE_2: [u32; 1093] = [
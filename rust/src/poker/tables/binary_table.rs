@@ -0,0 +1,283 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/BinaryTable) | Lazily loaded binary lookup table
+
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use crate::error::Result;
+use crate::poker::tables::{ojp_table_dir, ojp_read_binary_table, TableManifest};
+#[cfg(feature = "mmap")]
+use crate::poker::tables::{MappedTable, ojp_map_binary_table,
+    ojp_uncompressed_name};
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/TableSource) | How a binary table is loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableSource {
+    /// Decompress the `.bin.gz` file into memory
+    Compressed,
+    /// Map the uncompressed `.bin` file read-only (needs the "mmap" feature).
+    /// The first map reads the whole file to check it against the
+    /// manifest's SHA-256 and records a stamp beside it; later maps of the
+    /// unchanged file (same size and modification time) skip the check and
+    /// start almost instantly. A change that keeps both isn't noticed
+    /// until the stamp is removed, and if the stamp can't be written,
+    /// every process pays for the full check.
+    Mapped,
+    /// Map the `.bin` file if present and the "mmap" feature is enabled,
+    /// else decompress the `.bin.gz` file
+    #[default]
+    Auto,
+}
+
+#[derive(Debug)]
+enum TableData {
+    Owned(Vec<u16>),
+    #[cfg(feature = "mmap")]
+    Mapped(MappedTable),
+}
+
+impl TableData {
+    fn as_slice(&self) -> &[u16] {
+        match self {
+            TableData::Owned(v) => v,
+            #[cfg(feature = "mmap")]
+            TableData::Mapped(m) => m,
+        }
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/BinaryTable) | Lazily loaded binary lookup table
+///
+/// Loaded from the table directory (see [ojp_table_dir]) the first time it
+/// is used, either decompressed or memory-mapped according to its
+/// [TableSource]. Indexing it directly panics if the table can't be
+/// loaded; call [BinaryTable::try_load] first to get an error instead, or
+/// [BinaryTable::is_available] to check without loading.
+/// ```rust
+/// use onejoker::poker::tables::{BinaryTable, TableSource};
+///
+/// static TABLE: BinaryTable = BinaryTable::new("ojp_xx_table_1.bin.gz", 100);
+///
+/// assert_eq!(TABLE.name(), "ojp_xx_table_1.bin.gz");
+/// assert!(! TABLE.is_loaded());
+/// assert_eq!(TABLE.table_source(), TableSource::Auto);
+/// if TABLE.try_load().is_ok() {
+///     println!("{}", TABLE[42]);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BinaryTable {
+    name: &'static str,
+    size: usize,
+    source: TableSource,
+    table: OnceLock<TableData>,
+    loading: Mutex<()>,
+}
+
+impl BinaryTable {
+    /// New table with the given `.bin.gz` file name and number of entries
+    pub const fn new(name: &'static str, size: usize) -> BinaryTable {
+        BinaryTable { name, size, source: TableSource::Auto,
+            table: OnceLock::new(), loading: Mutex::new(()) }
+    }
+
+    /// Same table loaded from the given source
+    pub const fn source(mut self, source: TableSource) -> BinaryTable {
+        self.source = source;
+        self
+    }
+
+    /// How the table will be loaded
+    pub fn table_source(&self) -> TableSource {
+        self.source
+    }
+
+    /// File name
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Number of entries
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Has the table already been loaded?
+    pub fn is_loaded(&self) -> bool {
+        self.table.get().is_some()
+    }
+
+    /// Was the table loaded by memory-mapping it?
+    pub fn is_mapped(&self) -> bool {
+        match self.table.get() {
+            #[cfg(feature = "mmap")]
+            Some(TableData::Mapped(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Is the table loaded, or present in the table directory and listed
+    /// in its manifest (if any) with the right size? The checksum is only checked
+    /// when the table is actually loaded, whether decompressed or mapped.
+    pub fn is_available(&self) -> bool {
+        if self.is_loaded() { return true; }
+        let Ok(dir) = ojp_table_dir() else { return false; };
+        self.is_available_in(&dir)
+    }
+
+    /// Is the table present in the given directory? With no manifest in
+    /// the directory, the size is only checked when loaded.
+    pub fn is_available_in(&self, dir: &Path) -> bool {
+        let Ok(m) = TableManifest::read_if_present(dir) else { return false; };
+        if let Some(m) = m {
+            let Some(e) = m.get(self.name) else { return false; };
+            if e.entries != self.size { return false; }
+        }
+
+        match self.source {
+            TableSource::Compressed => dir.join(self.name).is_file(),
+            TableSource::Mapped => self.has_uncompressed(dir),
+            TableSource::Auto => self.has_uncompressed(dir) ||
+                dir.join(self.name).is_file(),
+        }
+    }
+
+    #[cfg(feature = "mmap")]
+    fn has_uncompressed(&self, dir: &Path) -> bool {
+        dir.join(ojp_uncompressed_name(self.name)).is_file()
+    }
+
+    #[cfg(not(feature = "mmap"))]
+    fn has_uncompressed(&self, _dir: &Path) -> bool {
+        false
+    }
+
+    /// Load the table if not already loaded
+    pub fn try_load(&self) -> Result<&[u16]> {
+        if let Some(t) = self.table.get() { return Ok(t.as_slice()); }
+        self.try_load_from(&ojp_table_dir()?)
+    }
+
+    /// Load the table from the given directory if not already loaded
+    pub fn try_load_from(&self, dir: &Path) -> Result<&[u16]> {
+        // Tables can be huge, so make sure only one thread reads the file
+        let _guard = self.loading.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(t) = self.table.get() { return Ok(t.as_slice()); }
+
+        let t = self.read(dir)?;
+        Ok(self.table.get_or_init(|| t).as_slice())
+    }
+
+    #[cfg(feature = "mmap")]
+    fn read(&self, dir: &Path) -> Result<TableData> {
+        let map = match self.source {
+            TableSource::Compressed => false,
+            TableSource::Mapped => true,
+            TableSource::Auto => self.has_uncompressed(dir),
+        };
+        if map {
+            Ok(TableData::Mapped(ojp_map_binary_table(dir, self.name, self.size)?))
+        } else {
+            Ok(TableData::Owned(ojp_read_binary_table(dir, self.name, self.size)?))
+        }
+    }
+
+    #[cfg(not(feature = "mmap"))]
+    fn read(&self, dir: &Path) -> Result<TableData> {
+        if TableSource::Mapped == self.source {
+            return Err(crate::error::Error::IO(format!(
+                "{} needs the \"mmap\" feature", self.name)));
+        }
+        Ok(TableData::Owned(ojp_read_binary_table(dir, self.name, self.size)?))
+    }
+}
+
+impl std::ops::Deref for BinaryTable {
+    type Target = [u16];
+
+    fn deref(&self) -> &[u16] {
+        self.try_load().unwrap_or_else(|e|
+            panic!("failed to load lookup table: {}", e))
+    }
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use crate::poker::tables::{TableManifestEntry, ojp_table_sha256};
+
+    #[test]
+    fn test_binary_table() -> Result<()> {
+        static TABLE: BinaryTable = BinaryTable::new("ojp_bt_table_1.bin.gz", 4);
+
+        let dir = std::env::temp_dir()
+            .join(format!("ojp_binary_table_test_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        assert!(! TABLE.is_available_in(&dir));
+        assert!(TABLE.try_load_from(&dir).is_err());
+        assert!(! TABLE.is_loaded());
+
+        let bytes: Vec<u8> = [0u16, 5, 6, 7].iter()
+            .flat_map(|v| v.to_le_bytes()).collect();
+        let mut w = GzEncoder::new(fs::File::create(dir.join(TABLE.name()))?,
+            Compression::default());
+        w.write_all(&bytes)?;
+        w.finish()?;
+
+        let mut m = TableManifest::new();
+        m.set(TableManifestEntry { name: TABLE.name().into(),
+            entries: 4, sha256: ojp_table_sha256(&bytes) });
+        m.write(&dir)?;
+        assert!(TABLE.is_available_in(&dir));
+
+        assert_eq!(TABLE.try_load_from(&dir)?.len(), 4);
+        assert!(TABLE.is_loaded());
+        assert!(TABLE.is_available());
+        assert_eq!(TABLE[2], 6);
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_binary_table_mapped() -> Result<()> {
+        static MAPPED: BinaryTable = BinaryTable::new("ojp_bm_table_1.bin.gz", 3)
+            .source(TableSource::Mapped);
+        static AUTO: BinaryTable = BinaryTable::new("ojp_bm_table_1.bin.gz", 3);
+        static COMPRESSED: BinaryTable = BinaryTable::new("ojp_bm_table_1.bin.gz", 3)
+            .source(TableSource::Compressed);
+
+        let dir = std::env::temp_dir()
+            .join(format!("ojp_binary_table_mapped_test_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+
+        let bytes: Vec<u8> = [0u16, 8, 9].iter()
+            .flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(dir.join("ojp_bm_table_1.bin"), &bytes)?;
+
+        let mut m = TableManifest::new();
+        m.set(TableManifestEntry { name: MAPPED.name().into(),
+            entries: 3, sha256: ojp_table_sha256(&bytes) });
+        m.write(&dir)?;
+
+        assert!(MAPPED.is_available_in(&dir));
+        assert!(AUTO.is_available_in(&dir));
+        assert!(! COMPRESSED.is_available_in(&dir));
+        assert!(COMPRESSED.try_load_from(&dir).is_err());
+
+        assert_eq!(MAPPED.try_load_from(&dir)?, &[0, 8, 9]);
+        assert!(MAPPED.is_mapped());
+        assert_eq!(AUTO.try_load_from(&dir)?[2], 9);
+        assert!(AUTO.is_mapped());
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
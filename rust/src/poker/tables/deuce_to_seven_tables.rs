@@ -1,32 +1,25 @@
 //! [wiki](https://github.com/lcrocker/ojpoker/wiki/Deuce_To_Seven_Tables) | Deuce-to-seven lookup tables
 
-use std::sync::LazyLock;
-use crate::poker::tables::ojp_load_binary_table;
+use crate::poker::tables::BinaryTable;
 
 const MP5_SIZE: usize = 2598960;    // 52c5
 const MP7_SIZE: usize = 133784560;  // 52c7
 
 /// Map from perfect hash to equivalence class
-pub static OJP_KC_TABLE_1: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_kc_table_1.bin.gz", MP5_SIZE + 1)
-});
+pub static OJP_KC_TABLE_1: BinaryTable =
+    BinaryTable::new("ojp_kc_table_1.bin.gz", MP5_SIZE + 1);
 
 /// Map from perfect hash to equivalence class
 /// Deuce-to-seven tables are identical to ace-to-six tables if correct
 /// deck and hash functions are used.
-pub static OJP_LL_TABLE_1: LazyLock<&'static Vec<u16>> = LazyLock::new(|| {
-    &*OJP_KC_TABLE_1
-});
+pub static OJP_LL_TABLE_1: &BinaryTable = &OJP_KC_TABLE_1;
 
 /// Map from perfect hash to equivalence class
-pub static OJP_KC_TABLE_3: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_kc_table_3.bin.gz", MP7_SIZE + 1)
-});
+pub static OJP_KC_TABLE_3: BinaryTable =
+    BinaryTable::new("ojp_kc_table_3.bin.gz", MP7_SIZE + 1);
 
 /// Map from perfect hash to equivalence class
-pub static OJP_LL_TABLE_3: LazyLock<&'static Vec<u16>> = LazyLock::new(|| {
-    &*OJP_KC_TABLE_3
-});
+pub static OJP_LL_TABLE_3: &BinaryTable = &OJP_KC_TABLE_3;
 
 /// Map from equivalence class to hand level and card ranks
 pub static OJP_KC_TABLE_2: [u32; 7463] = [
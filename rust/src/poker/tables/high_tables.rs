@@ -1,20 +1,17 @@
 //! [wiki](https://github.com/lcrocker/ojpoker/wiki/High_Hand_Tables) | High hand lookup tables
 
-use std::sync::LazyLock;
-use crate::poker::tables::ojp_load_binary_table;
+use crate::poker::tables::BinaryTable;
 
 const MP5_SIZE: usize = 2598960;    // 52c5
 const MP7_SIZE: usize = 133784560;  // 52c7
 
 /// Map from perfect hash to equivalence class
-pub static OJP_HH_TABLE_1: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_hh_table_1.bin.gz", MP5_SIZE + 1)
-});
+pub static OJP_HH_TABLE_1: BinaryTable =
+    BinaryTable::new("ojp_hh_table_1.bin.gz", MP5_SIZE + 1);
 
 /// Map from perfect hash to equivalence class
-pub static OJP_HH_TABLE_3: LazyLock<Vec<u16>> = LazyLock::new(|| {
-    ojp_load_binary_table("ojp_hh_table_3.bin.gz", MP7_SIZE + 1)
-});
+pub static OJP_HH_TABLE_3: BinaryTable =
+    BinaryTable::new("ojp_hh_table_3.bin.gz", MP7_SIZE + 1);
 
 /// Map from equivalence class to hand level and card ranks
 pub static OJP_HH_TABLE_2: [u32; 7476] = [
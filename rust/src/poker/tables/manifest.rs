@@ -0,0 +1,251 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Table_Manifest) | Sizes and checksums of binary lookup tables
+//!
+//! Binary tables are downloaded separately from the code, so each one is
+//! listed in a manifest file in the same directory giving its number of
+//! entries and the SHA-256 of its decompressed contents. The checksum is of
+//! the decompressed data so that rebuilding a table with a different
+//! version of the compressor still matches.
+//!
+//! Tables installed before there was a manifest still load if the
+//! directory has no manifest at all, checked by size only, with a warning.
+
+use std::fs;
+use std::io::{Read, BufReader};
+use std::path::Path;
+use flate2::read::GzDecoder;
+use sha2::{Sha256, Digest};
+use crate::error::{Error, Result};
+
+/// Name of the manifest file in the table directory
+pub const OJP_TABLE_MANIFEST: &str = "ojp_manifest.txt";
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/TableManifestEntry) | Expected size and checksum of one table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableManifestEntry {
+    /// File name, e.g. "ojp_hh_table_1.bin.gz"
+    pub name: String,
+    /// Number of u16 entries
+    pub entries: usize,
+    /// SHA-256 of the decompressed table, as lowercase hex
+    pub sha256: String,
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/TableManifest) | Manifest of binary lookup tables
+///
+/// The file has one line per table with its name, number of entries, and
+/// checksum, separated by spaces. Blank lines and lines beginning with `#`
+/// are ignored.
+/// ```rust
+/// use onejoker::poker::tables::TableManifest;
+///
+/// let text = "# name entries sha256\nojp_xx_table_1.bin.gz 3 abcd\n";
+/// let m: TableManifest = text.parse().unwrap();
+/// assert_eq!(m.get("ojp_xx_table_1.bin.gz").unwrap().entries, 3);
+/// assert!(m.get("ojp_yy_table_1.bin.gz").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableManifest {
+    entries: Vec<TableManifestEntry>,
+}
+
+impl TableManifest {
+    /// Empty manifest
+    pub fn new() -> TableManifest {
+        TableManifest::default()
+    }
+
+    /// Read manifest from the given table directory
+    pub fn read(dir: &Path) -> Result<TableManifest> {
+        let path = dir.join(OJP_TABLE_MANIFEST);
+        let text = fs::read_to_string(&path)
+            .map_err(|e| Error::IO(format!("{}: {}", path.display(), e)))?;
+        text.parse()
+    }
+
+    /// Read manifest from the given table directory, or `None` if there
+    /// isn't one
+    pub fn read_if_present(dir: &Path) -> Result<Option<TableManifest>> {
+        if ! dir.join(OJP_TABLE_MANIFEST).exists() {
+            return Ok(None);
+        }
+        Ok(Some(TableManifest::read(dir)?))
+    }
+
+    /// Write manifest to the given table directory
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        fs::write(dir.join(OJP_TABLE_MANIFEST), self.to_string())?;
+        Ok(())
+    }
+
+    /// Entry for the given file name, if any
+    pub fn get(&self, name: &str) -> Option<&TableManifestEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    /// Add entry, replacing any with the same name
+    pub fn set(&mut self, entry: TableManifestEntry) {
+        match self.entries.iter_mut().find(|e| e.name == entry.name) {
+            Some(e) => *e = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Iterate over entries in file order
+    pub fn iter(&self) -> impl Iterator<Item = &TableManifestEntry> {
+        self.entries.iter()
+    }
+}
+
+impl std::str::FromStr for TableManifest {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<TableManifest> {
+        let mut m = TableManifest::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') { continue; }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if 3 != fields.len() {
+                return Err(Error::ParseOther(
+                    format!("bad manifest line: {}", line)));
+            }
+            let entries = fields[1].parse::<usize>().map_err(|_|
+                Error::ParseOther(format!("bad manifest size: {}", fields[1])))?;
+
+            m.set(TableManifestEntry {
+                name: fields[0].to_string(),
+                entries,
+                sha256: fields[2].to_lowercase(),
+            });
+        }
+        Ok(m)
+    }
+}
+
+impl std::fmt::Display for TableManifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "# name entries sha256")?;
+        for e in self.entries.iter() {
+            writeln!(f, "{} {} {}", e.name, e.entries, e.sha256)?;
+        }
+        Ok(())
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_table_sha256) | SHA-256 of table contents as lowercase hex
+/// ```rust
+/// use onejoker::poker::tables::ojp_table_sha256;
+///
+/// assert_eq!(ojp_table_sha256(b"abc"),
+///     "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+/// ```
+pub fn ojp_table_sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Manifest entry for the table, checked against `size`, or `None` with a
+// warning if there's no manifest to check against
+pub(crate) fn ojp_manifest_entry(dir: &Path, name: &str, size: usize)
+-> Result<Option<TableManifestEntry>> {
+    let Some(manifest) = TableManifest::read_if_present(dir)? else {
+        eprintln!("warning: no {} in {}, checking {} by size only",
+            OJP_TABLE_MANIFEST, dir.display(), name);
+        return Ok(None);
+    };
+    let Some(entry) = manifest.get(name) else {
+        return Err(Error::IO(format!("{} is not in {}", name,
+            OJP_TABLE_MANIFEST)));
+    };
+    if entry.entries != size {
+        return Err(Error::IO(format!("{} has {} entries in {}, expected {}",
+            name, entry.entries, OJP_TABLE_MANIFEST, size)));
+    }
+    Ok(Some(entry.clone()))
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_read_binary_table) | Decompress and verify binary lookup table
+///
+/// Reads the named table from the given directory, checking its size
+/// against both `size` and the manifest and its contents against the
+/// manifest checksum. If the directory has no manifest, only the size is
+/// checked.
+pub fn ojp_read_binary_table(dir: &Path, name: &str, size: usize)
+-> Result<Vec<u16>> {
+    let entry = ojp_manifest_entry(dir, name, size)?;
+    let path = dir.join(name);
+    let file = fs::File::open(&path)
+        .map_err(|e| Error::IO(format!("{}: {}", path.display(), e)))?;
+
+    let mut reader = BufReader::new(file);
+    let mut decoder = GzDecoder::new(&mut reader);
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(2 * size);
+    let nread = decoder.read_to_end(&mut bytes)?;
+
+    if nread != 2 * size {
+        return Err(Error::IO(format!("{} has {} bytes, expected {}",
+            name, nread, 2 * size)));
+    }
+    if entry.is_some_and(|e| ojp_table_sha256(&bytes) != e.sha256) {
+        return Err(Error::IO(format!("{} does not match checksum in {}",
+            name, OJP_TABLE_MANIFEST)));
+    }
+    // Entries are stored little-endian
+    Ok(bytes.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect())
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    #[test]
+    fn test_table_manifest() -> Result<()> {
+        let dir = std::env::temp_dir()
+            .join(format!("ojp_manifest_test_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+
+        let table: Vec<u16> = vec![0, 1, 2, 0xFFFF, 7];
+        let bytes: Vec<u8> = table.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let mut w = GzEncoder::new(fs::File::create(dir.join("t.bin.gz"))?,
+            Compression::default());
+        w.write_all(&bytes)?;
+        w.finish()?;
+
+        // No manifest: size only
+        assert_eq!(TableManifest::read_if_present(&dir)?, None);
+        assert_eq!(ojp_read_binary_table(&dir, "t.bin.gz", 5)?, table);
+        assert!(ojp_read_binary_table(&dir, "t.bin.gz", 6).is_err());
+
+        let mut m = TableManifest::new();
+        m.set(TableManifestEntry { name: "t.bin.gz".into(), entries: 5,
+            sha256: ojp_table_sha256(&bytes) });
+        m.write(&dir)?;
+        assert_eq!(TableManifest::read(&dir)?, m);
+        assert_eq!(TableManifest::read_if_present(&dir)?, Some(m.clone()));
+
+        assert_eq!(ojp_read_binary_table(&dir, "t.bin.gz", 5)?, table);
+        assert!(ojp_read_binary_table(&dir, "t.bin.gz", 6).is_err());
+        assert!(ojp_read_binary_table(&dir, "u.bin.gz", 5).is_err());
+
+        m.set(TableManifestEntry { name: "t.bin.gz".into(), entries: 5,
+            sha256: ojp_table_sha256(b"something else") });
+        assert_eq!(m.iter().count(), 1);
+        m.write(&dir)?;
+        assert!(ojp_read_binary_table(&dir, "t.bin.gz", 5).is_err());
+
+        assert!("t.bin.gz 5".parse::<TableManifest>().is_err());
+        assert!("t.bin.gz five abcd".parse::<TableManifest>().is_err());
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/MappedTable) | Memory-mapped uncompressed lookup tables
+//!
+//! The uncompressed format is just the decompressed `.bin.gz` file (so
+//! `gunzip -k ojp_hh_table_3.bin.gz` makes one). Mapping it read-only
+//! costs almost nothing at startup, and the pages are shared by every
+//! process using the same file.
+//!
+//! Checking the manifest checksum means reading the whole file, so it's
+//! done once: a file that matches gets a stamp file beside it (e.g.
+//! `ojp_hh_table_3.bin.sha256`) recording its size, modification time, and
+//! checksum, and later maps skip the check while all three still agree.
+//! If the stamp can't be written, every map checks the whole file.
+
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use memmap2::Mmap;
+use crate::error::{Error, Result};
+use crate::poker::tables::{ojp_manifest_entry, ojp_table_sha256, OJP_TABLE_MANIFEST};
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/MappedTable) | Read-only memory-mapped lookup table
+#[derive(Debug)]
+pub struct MappedTable {
+    map: Mmap,
+}
+
+impl std::ops::Deref for MappedTable {
+    type Target = [u16];
+
+    fn deref(&self) -> &[u16] {
+        // Maps are page-aligned, and the length was checked when mapped.
+        unsafe {
+            std::slice::from_raw_parts(self.map.as_ptr() as *const u16,
+                self.map.len() / 2)
+        }
+    }
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_map_binary_table) | Map uncompressed binary lookup table
+///
+/// Maps the uncompressed file for the table `name` (a `.bin.gz` name as
+/// listed in the manifest) from the given directory, checking its size
+/// against both `size` and the manifest, and its SHA-256 against the
+/// manifest unless a stamp shows it was already checked unchanged. If
+/// the directory has no manifest, only the size is checked. The file must
+/// not be modified while mapped.
+pub fn ojp_map_binary_table(dir: &Path, name: &str, size: usize)
+-> Result<MappedTable> {
+    if cfg!(target_endian = "big") {
+        return Err(Error::IO("mapped tables need a little-endian machine".into()));
+    }
+    let entry = ojp_manifest_entry(dir, name, size)?;
+    let path = dir.join(ojp_uncompressed_name(name));
+    let file = fs::File::open(&path)
+        .map_err(|e| Error::IO(format!("{}: {}", path.display(), e)))?;
+
+    let meta = file.metadata()?;
+    if meta.len() != 2 * size as u64 {
+        return Err(Error::IO(format!("{} has {} bytes, expected {}",
+            path.display(), meta.len(), 2 * size)));
+    }
+    let map = unsafe { Mmap::map(&file)? };
+
+    if let Some(e) = entry {
+        let stamp_path = stamp_name(&path);
+        let stamp = stamp_text(&meta, &e.sha256);
+
+        if stamp.is_none() || stamp != fs::read_to_string(&stamp_path).ok() {
+            if ojp_table_sha256(&map) != e.sha256 {
+                return Err(Error::IO(format!("{} does not match checksum in {}",
+                    path.display(), OJP_TABLE_MANIFEST)));
+            }
+            // Best effort: the table directory may be read-only
+            if let Some(st) = stamp {
+                let _ = fs::write(&stamp_path, st);
+            }
+        }
+    }
+    Ok(MappedTable { map })
+}
+
+// Stamp file recording a checked uncompressed table
+fn stamp_name(path: &Path) -> std::path::PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(".sha256");
+    s.into()
+}
+
+// Size, modification time, and checksum, or None if the file system
+// doesn't give modification times
+fn stamp_text(meta: &fs::Metadata, sha256: &str) -> Option<String> {
+    let t = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(format!("{} {} {}\n", meta.len(), t.as_nanos(), sha256))
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_uncompressed_name) | File name of uncompressed table
+/// ```rust
+/// use onejoker::poker::tables::ojp_uncompressed_name;
+///
+/// assert_eq!(ojp_uncompressed_name("ojp_hh_table_3.bin.gz"), "ojp_hh_table_3.bin");
+/// ```
+pub fn ojp_uncompressed_name(name: &str) -> &str {
+    name.strip_suffix(".gz").unwrap_or(name)
+}
+
+/*
+ * CODE ENDS HERE
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::tables::{TableManifest, TableManifestEntry};
+
+    #[test]
+    fn test_mapped_table() -> Result<()> {
+        let dir = std::env::temp_dir()
+            .join(format!("ojp_mapped_table_test_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+
+        let table: Vec<u16> = vec![0, 1, 0x1234, 0xFFFF, 9];
+        let bytes: Vec<u8> = table.iter().flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(dir.join("t.bin"), &bytes)?;
+        assert_eq!(&ojp_map_binary_table(&dir, "t.bin.gz", 5)?[..], &table[..]);
+        assert!(ojp_map_binary_table(&dir, "t.bin.gz", 4).is_err());
+
+        let mut m = TableManifest::new();
+        m.set(TableManifestEntry { name: "t.bin.gz".into(), entries: 5,
+            sha256: ojp_table_sha256(&bytes) });
+        m.write(&dir)?;
+
+        let t = ojp_map_binary_table(&dir, "t.bin.gz", 5)?;
+        assert_eq!(&t[..], &table[..]);
+        assert!(ojp_map_binary_table(&dir, "t.bin.gz", 4).is_err());
+
+        m.set(TableManifestEntry { name: "t.bin.gz".into(), entries: 4,
+            sha256: ojp_table_sha256(&bytes) });
+        m.write(&dir)?;
+        assert!(ojp_map_binary_table(&dir, "t.bin.gz", 4).is_err());
+
+        // Right size, wrong contents
+        m.set(TableManifestEntry { name: "t.bin.gz".into(), entries: 5,
+            sha256: ojp_table_sha256(&bytes) });
+        m.write(&dir)?;
+        let mut bad = bytes.clone();
+        bad[3] ^= 1;
+        fs::write(dir.join("t.bin"), &bad)?;
+        assert!(ojp_map_binary_table(&dir, "t.bin.gz", 5).is_err());
+
+        // Checked once, then stamped
+        fs::write(dir.join("t.bin"), &bytes)?;
+        assert!(ojp_map_binary_table(&dir, "t.bin.gz", 5).is_ok());
+        let stamp = fs::read_to_string(dir.join("t.bin.sha256"))?;
+        assert!(stamp.ends_with(&format!(" {}\n", ojp_table_sha256(&bytes))));
+        assert!(ojp_map_binary_table(&dir, "t.bin.gz", 5).is_ok());
+
+        // A stamp for other contents doesn't count
+        fs::write(dir.join("t.bin.sha256"), stamp.replace(
+            &ojp_table_sha256(&bytes), &ojp_table_sha256(&bad)))?;
+        assert!(ojp_map_binary_table(&dir, "t.bin.gz", 5).is_ok());
+        assert_eq!(fs::read_to_string(dir.join("t.bin.sha256"))?, stamp);
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -3,6 +3,12 @@
 //! files used must be downloaded from the "releases" area of the repository.
 //! See [README](https://github.com/lcrocker/ojpoker/blob/main/data/releases/README.md)
 //! for details.
+//!
+//! With the "mmap" feature, uncompressed copies of the tables are mapped
+//! read-only instead of being decompressed into memory, if present.
+
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+use crate::error::{Error, Result};
 
 #[cfg(feature = "high-hand-tables")]
 pub mod high_tables;
@@ -16,6 +22,75 @@ pub mod deuce_to_seven_tables;
 #[cfg(feature = "badugi-tables")]
 pub mod badugi_tables;
 
+#[cfg(feature = "three-card-tables")]
+pub mod three_card_tables;
+
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub mod manifest;
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub use manifest::*;
+
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub mod binary_table;
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub use binary_table::*;
+
+#[cfg(all(feature = "flate2", feature = "sha2", feature = "mmap"))]
+pub mod mapped_table;
+#[cfg(all(feature = "flate2", feature = "sha2", feature = "mmap"))]
+pub use mapped_table::*;
+
+/// Environment variable giving the directory holding binary lookup tables
+pub const OJP_TABLE_DIR_ENV: &str = "ONEJOKER_TABLE_DIR";
+
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+static TABLE_DIR: std::sync::RwLock<Option<std::path::PathBuf>> =
+    std::sync::RwLock::new(None);
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_set_table_dir) | Set directory holding binary lookup tables
+///
+/// Overrides the environment variable. Only affects tables not yet loaded,
+/// so call this at startup.
+/// ```rust
+/// use onejoker::poker::tables::{ojp_set_table_dir, ojp_table_dir};
+///
+/// ojp_set_table_dir("/opt/data");
+/// assert_eq!(ojp_table_dir().unwrap().to_str(), Some("/opt/data"));
+/// ```
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub fn ojp_set_table_dir<P: AsRef<std::path::Path>>(dir: P) {
+    let mut d = TABLE_DIR.write().unwrap_or_else(|e| e.into_inner());
+    *d = Some(dir.as_ref().to_path_buf());
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_table_dir) | Directory holding binary lookup tables
+///
+/// The directory given to [ojp_set_table_dir] if any, else the one named
+/// by the `ONEJOKER_TABLE_DIR` environment variable, else
+/// `$CARGO_HOME/onejoker`.
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub fn ojp_table_dir() -> Result<std::path::PathBuf> {
+    if let Some(d) = TABLE_DIR.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Ok(d.clone());
+    }
+    if let Some(d) = std::env::var_os(OJP_TABLE_DIR_ENV) {
+        if ! d.is_empty() { return Ok(d.into()); }
+    }
+    let mut path = home::cargo_home().map_err(|e|
+        Error::IO(format!("no table directory: {}", e)))?;
+    path.push("onejoker");
+    Ok(path)
+}
+
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_try_load_binary_table) | Decompress and load binary lookup table
+///
+/// Like [ojp_load_binary_table], but returns `OjError::IO` if the table is
+/// missing or doesn't match the manifest.
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub fn ojp_try_load_binary_table(name: &str, size: usize) -> Result<Vec<u16>> {
+    ojp_read_binary_table(&ojp_table_dir()?, name, size)
+}
+
 /// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_load_binary_table) | Decompress and load binary loopup table
 ///
 /// Yes, I'm using "expect" here.
@@ -23,44 +98,91 @@ pub mod badugi_tables;
 /// evaluators because they produce different values, violating user
 /// expectation and complicating the tests.
 /// Also I want the user the know that the tables are missing.
-#[cfg(any(feature = "high-hand-tables",
-    feature = "ace-to-five-tables",
-    feature = "deuce-to-seven-tables",
-    feature = "badugi-tables"))]
+/// Same goes for tables that don't match the manifest.
+/// Use [ojp_try_load_binary_table] or [ojp_try_load_tables] to handle
+/// missing tables yourself.
+#[cfg(all(feature = "flate2", feature = "sha2"))]
 pub fn ojp_load_binary_table(name: &str, size: usize) -> Vec<u16> {
-    use std::fs;
-    use std::io::{Read, BufReader};
-    use std::path::PathBuf;
-    use home::cargo_home;
-    use flate2::read::GzDecoder;
-
-    let mut path: PathBuf = cargo_home()
-        .expect("failed to get cargo home directory");
-
-    path.push("onejoker");
-    path.push(name);
+    ojp_try_load_binary_table(name, size)
+        .unwrap_or_else(|e| panic!("failed to load lookup table: {}", e))
+}
 
-    let file = fs::File::open(&path)
-        .expect("failed to open binary file for lookup table");
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_binary_tables) | Binary lookup tables enabled in this build
+///
+/// Check [BinaryTable::is_available] on each to see which can be loaded.
+/// ```rust
+/// use onejoker::poker::tables::ojp_binary_tables;
+///
+/// for t in ojp_binary_tables() {
+///     println!("{}: {}", t.name(),
+///         if t.is_available() { "available" } else { "missing" });
+/// }
+/// ```
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub fn ojp_binary_tables() -> Vec<&'static BinaryTable> {
+    #[allow(unused_mut)]
+    let mut v: Vec<&'static BinaryTable> = Vec::new();
 
-    let mut reader = BufReader::new(file);
-    let mut decoder = GzDecoder::new(&mut reader);
+    #[cfg(feature = "high-hand-tables")]
+    v.extend([&high_tables::OJP_HH_TABLE_1, &high_tables::OJP_HH_TABLE_3]);
+    #[cfg(feature = "ace-to-five-tables")]
+    v.extend([&ace_to_five_tables::OJP_A5_TABLE_1,
+        &ace_to_five_tables::OJP_A5_TABLE_3]);
+    #[cfg(feature = "deuce-to-seven-tables")]
+    v.extend([&deuce_to_seven_tables::OJP_KC_TABLE_1,
+        &deuce_to_seven_tables::OJP_KC_TABLE_3]);
+    #[cfg(feature = "badugi-tables")]
+    v.extend([&badugi_tables::OJP_BG_TABLE_1, &badugi_tables::OJP_BG_TABLE_3]);
+    v
+}
 
-    let mut table: Vec<u16> = Vec::with_capacity(size);
-    let ptr = table.as_mut_ptr();
-    let cap = table.capacity() * 2;
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_scale_tables) | Binary lookup tables used to evaluate a scale
+///
+/// Empty if the scale doesn't use binary tables in this build.
+/// ```rust
+/// use onejoker::prelude::*;
+/// use onejoker::poker::tables::ojp_scale_tables;
+///
+/// assert!(ojp_scale_tables(Scale::PaiGow).is_empty());
+/// ```
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub fn ojp_scale_tables(g: crate::poker::Scale) -> Vec<&'static BinaryTable> {
+    #[cfg(any(feature = "high-hand-tables", feature = "ace-to-five-tables",
+        feature = "deuce-to-seven-tables", feature = "badugi-tables"))]
+    use crate::poker::Scale;
 
-    std::mem::forget(table);
-    let mut bytes: Vec<u8> = unsafe {
-        Vec::from_raw_parts(ptr as *mut u8, 0, cap)
-    };
-    let nread = decoder.read_to_end(&mut bytes).unwrap();
-    debug_assert!(nread == 2 * size);
+    #[allow(unused_mut)]
+    let mut v: Vec<&'static BinaryTable> = Vec::new();
 
-    let len = bytes.len() / 2;
-    let cap = bytes.capacity() / 2;
-    let ptr = bytes.as_mut_ptr() as *mut u16;
+    match g {
+        #[cfg(feature = "high-hand-tables")]
+        Scale::HighHand | Scale::HighHandBug =>
+            v.extend([&high_tables::OJP_HH_TABLE_1, &high_tables::OJP_HH_TABLE_3]),
+        #[cfg(feature = "ace-to-five-tables")]
+        Scale::AceToFive | Scale::AceToFiveBug | Scale::ActionRazz =>
+            v.extend([&ace_to_five_tables::OJP_A5_TABLE_1,
+                &ace_to_five_tables::OJP_A5_TABLE_3]),
+        #[cfg(feature = "deuce-to-seven-tables")]
+        Scale::DeuceToSeven =>
+            v.extend([&deuce_to_seven_tables::OJP_KC_TABLE_1,
+                &deuce_to_seven_tables::OJP_KC_TABLE_3]),
+        #[cfg(feature = "deuce-to-seven-tables")]
+        Scale::AceToSix => v.push(deuce_to_seven_tables::OJP_LL_TABLE_1),
+        #[cfg(feature = "badugi-tables")]
+        Scale::Badugi | Scale::Badeucy => v.push(&badugi_tables::OJP_BG_TABLE_1),
+        _ => (),
+    }
+    v
+}
 
-    std::mem::forget(bytes);
-    unsafe { Vec::from_raw_parts(ptr, len, cap) }
+/// [wiki](https://github.com/lcrocker/ojpoker/wiki/ojp_try_load_tables) | Load all binary lookup tables enabled in this build
+///
+/// Call at startup to find out about missing tables right away, rather than
+/// panicking at the first hand evaluation that needs one.
+#[cfg(all(feature = "flate2", feature = "sha2"))]
+pub fn ojp_try_load_tables() -> Result<()> {
+    for t in ojp_binary_tables() {
+        t.try_load()?;
+    }
+    Ok(())
 }
@@ -0,0 +1,145 @@
+//! [wiki](https://github.com/lcrocker/ojpoker/wiki/Three_Card_Tables) | Three-card poker lookup tables
+
+use std::sync::LazyLock;
+use crate::cards::*;
+use crate::poker::*;
+
+/// Map from perfect hash to equivalence class
+///
+/// Small enough to build at startup from the reference evaluator, so
+/// there is no binary file to download.
+pub static OJP_TC_TABLE_1: LazyLock<Vec<u16>> = LazyLock::new(|| {
+    let mut table: Vec<u16> = vec![0; 22101];
+
+    for h in Deck::new(DeckType::English).combinations(3) {
+        let v = ojp_reference_evaluator(&h, Scale::ThreeCard);
+        let ec = OJP_TC_TABLE_2.binary_search(&v)
+            .expect("three-card value missing from class table");
+        table[ojh_bitfield_mp3_english(&h[..]) as usize] = ec as u16;
+    }
+    table
+});
+
+/// Map from equivalence class to hand level and card ranks
+pub const OJP_TC_TABLE_2: [u32; 742] = [
+    0x000000, 0x1FF000, 0x1FF111, 0x1FF222, 0x1FF444, 0x1FF555, 0x1FF666, 0x1FF777,
+    0x1FF888, 0x1FF999, 0x1FFAAA, 0x1FFBBB, 0x1FFCCC, 0x1FFDDD, 0x2FF012, 0x2FF124,
+    0x2FF245, 0x2FF456, 0x2FF567, 0x2FF678, 0x2FF789, 0x2FF89A, 0x2FF9AB, 0x2FFABC,
+    0x2FFBCD, 0x2FFCD0, 0x3FF012, 0x3FF124, 0x3FF245, 0x3FF456, 0x3FF567, 0x3FF678,
+    0x3FF789, 0x3FF89A, 0x3FF9AB, 0x3FFABC, 0x3FFBCD, 0x3FFCD0, 0x4FF014, 0x4FF015,
+    0x4FF016, 0x4FF017, 0x4FF018, 0x4FF019, 0x4FF01A, 0x4FF01B, 0x4FF01C, 0x4FF01D,
+    0x4FF024, 0x4FF025, 0x4FF026, 0x4FF027, 0x4FF028, 0x4FF029, 0x4FF02A, 0x4FF02B,
+    0x4FF02C, 0x4FF02D, 0x4FF045, 0x4FF046, 0x4FF047, 0x4FF048, 0x4FF049, 0x4FF04A,
+    0x4FF04B, 0x4FF04C, 0x4FF04D, 0x4FF056, 0x4FF057, 0x4FF058, 0x4FF059, 0x4FF05A,
+    0x4FF05B, 0x4FF05C, 0x4FF05D, 0x4FF067, 0x4FF068, 0x4FF069, 0x4FF06A, 0x4FF06B,
+    0x4FF06C, 0x4FF06D, 0x4FF078, 0x4FF079, 0x4FF07A, 0x4FF07B, 0x4FF07C, 0x4FF07D,
+    0x4FF089, 0x4FF08A, 0x4FF08B, 0x4FF08C, 0x4FF08D, 0x4FF09A, 0x4FF09B, 0x4FF09C,
+    0x4FF09D, 0x4FF0AB, 0x4FF0AC, 0x4FF0AD, 0x4FF0BC, 0x4FF0BD, 0x4FF125, 0x4FF126,
+    0x4FF127, 0x4FF128, 0x4FF129, 0x4FF12A, 0x4FF12B, 0x4FF12C, 0x4FF12D, 0x4FF145,
+    0x4FF146, 0x4FF147, 0x4FF148, 0x4FF149, 0x4FF14A, 0x4FF14B, 0x4FF14C, 0x4FF14D,
+    0x4FF156, 0x4FF157, 0x4FF158, 0x4FF159, 0x4FF15A, 0x4FF15B, 0x4FF15C, 0x4FF15D,
+    0x4FF167, 0x4FF168, 0x4FF169, 0x4FF16A, 0x4FF16B, 0x4FF16C, 0x4FF16D, 0x4FF178,
+    0x4FF179, 0x4FF17A, 0x4FF17B, 0x4FF17C, 0x4FF17D, 0x4FF189, 0x4FF18A, 0x4FF18B,
+    0x4FF18C, 0x4FF18D, 0x4FF19A, 0x4FF19B, 0x4FF19C, 0x4FF19D, 0x4FF1AB, 0x4FF1AC,
+    0x4FF1AD, 0x4FF1BC, 0x4FF1BD, 0x4FF1CD, 0x4FF246, 0x4FF247, 0x4FF248, 0x4FF249,
+    0x4FF24A, 0x4FF24B, 0x4FF24C, 0x4FF24D, 0x4FF256, 0x4FF257, 0x4FF258, 0x4FF259,
+    0x4FF25A, 0x4FF25B, 0x4FF25C, 0x4FF25D, 0x4FF267, 0x4FF268, 0x4FF269, 0x4FF26A,
+    0x4FF26B, 0x4FF26C, 0x4FF26D, 0x4FF278, 0x4FF279, 0x4FF27A, 0x4FF27B, 0x4FF27C,
+    0x4FF27D, 0x4FF289, 0x4FF28A, 0x4FF28B, 0x4FF28C, 0x4FF28D, 0x4FF29A, 0x4FF29B,
+    0x4FF29C, 0x4FF29D, 0x4FF2AB, 0x4FF2AC, 0x4FF2AD, 0x4FF2BC, 0x4FF2BD, 0x4FF2CD,
+    0x4FF457, 0x4FF458, 0x4FF459, 0x4FF45A, 0x4FF45B, 0x4FF45C, 0x4FF45D, 0x4FF467,
+    0x4FF468, 0x4FF469, 0x4FF46A, 0x4FF46B, 0x4FF46C, 0x4FF46D, 0x4FF478, 0x4FF479,
+    0x4FF47A, 0x4FF47B, 0x4FF47C, 0x4FF47D, 0x4FF489, 0x4FF48A, 0x4FF48B, 0x4FF48C,
+    0x4FF48D, 0x4FF49A, 0x4FF49B, 0x4FF49C, 0x4FF49D, 0x4FF4AB, 0x4FF4AC, 0x4FF4AD,
+    0x4FF4BC, 0x4FF4BD, 0x4FF4CD, 0x4FF568, 0x4FF569, 0x4FF56A, 0x4FF56B, 0x4FF56C,
+    0x4FF56D, 0x4FF578, 0x4FF579, 0x4FF57A, 0x4FF57B, 0x4FF57C, 0x4FF57D, 0x4FF589,
+    0x4FF58A, 0x4FF58B, 0x4FF58C, 0x4FF58D, 0x4FF59A, 0x4FF59B, 0x4FF59C, 0x4FF59D,
+    0x4FF5AB, 0x4FF5AC, 0x4FF5AD, 0x4FF5BC, 0x4FF5BD, 0x4FF5CD, 0x4FF679, 0x4FF67A,
+    0x4FF67B, 0x4FF67C, 0x4FF67D, 0x4FF689, 0x4FF68A, 0x4FF68B, 0x4FF68C, 0x4FF68D,
+    0x4FF69A, 0x4FF69B, 0x4FF69C, 0x4FF69D, 0x4FF6AB, 0x4FF6AC, 0x4FF6AD, 0x4FF6BC,
+    0x4FF6BD, 0x4FF6CD, 0x4FF78A, 0x4FF78B, 0x4FF78C, 0x4FF78D, 0x4FF79A, 0x4FF79B,
+    0x4FF79C, 0x4FF79D, 0x4FF7AB, 0x4FF7AC, 0x4FF7AD, 0x4FF7BC, 0x4FF7BD, 0x4FF7CD,
+    0x4FF89B, 0x4FF89C, 0x4FF89D, 0x4FF8AB, 0x4FF8AC, 0x4FF8AD, 0x4FF8BC, 0x4FF8BD,
+    0x4FF8CD, 0x4FF9AC, 0x4FF9AD, 0x4FF9BC, 0x4FF9BD, 0x4FF9CD, 0x4FFABD, 0x4FFACD,
+    0x5FF001, 0x5FF002, 0x5FF004, 0x5FF005, 0x5FF006, 0x5FF007, 0x5FF008, 0x5FF009,
+    0x5FF00A, 0x5FF00B, 0x5FF00C, 0x5FF00D, 0x5FF110, 0x5FF112, 0x5FF114, 0x5FF115,
+    0x5FF116, 0x5FF117, 0x5FF118, 0x5FF119, 0x5FF11A, 0x5FF11B, 0x5FF11C, 0x5FF11D,
+    0x5FF220, 0x5FF221, 0x5FF224, 0x5FF225, 0x5FF226, 0x5FF227, 0x5FF228, 0x5FF229,
+    0x5FF22A, 0x5FF22B, 0x5FF22C, 0x5FF22D, 0x5FF440, 0x5FF441, 0x5FF442, 0x5FF445,
+    0x5FF446, 0x5FF447, 0x5FF448, 0x5FF449, 0x5FF44A, 0x5FF44B, 0x5FF44C, 0x5FF44D,
+    0x5FF550, 0x5FF551, 0x5FF552, 0x5FF554, 0x5FF556, 0x5FF557, 0x5FF558, 0x5FF559,
+    0x5FF55A, 0x5FF55B, 0x5FF55C, 0x5FF55D, 0x5FF660, 0x5FF661, 0x5FF662, 0x5FF664,
+    0x5FF665, 0x5FF667, 0x5FF668, 0x5FF669, 0x5FF66A, 0x5FF66B, 0x5FF66C, 0x5FF66D,
+    0x5FF770, 0x5FF771, 0x5FF772, 0x5FF774, 0x5FF775, 0x5FF776, 0x5FF778, 0x5FF779,
+    0x5FF77A, 0x5FF77B, 0x5FF77C, 0x5FF77D, 0x5FF880, 0x5FF881, 0x5FF882, 0x5FF884,
+    0x5FF885, 0x5FF886, 0x5FF887, 0x5FF889, 0x5FF88A, 0x5FF88B, 0x5FF88C, 0x5FF88D,
+    0x5FF990, 0x5FF991, 0x5FF992, 0x5FF994, 0x5FF995, 0x5FF996, 0x5FF997, 0x5FF998,
+    0x5FF99A, 0x5FF99B, 0x5FF99C, 0x5FF99D, 0x5FFAA0, 0x5FFAA1, 0x5FFAA2, 0x5FFAA4,
+    0x5FFAA5, 0x5FFAA6, 0x5FFAA7, 0x5FFAA8, 0x5FFAA9, 0x5FFAAB, 0x5FFAAC, 0x5FFAAD,
+    0x5FFBB0, 0x5FFBB1, 0x5FFBB2, 0x5FFBB4, 0x5FFBB5, 0x5FFBB6, 0x5FFBB7, 0x5FFBB8,
+    0x5FFBB9, 0x5FFBBA, 0x5FFBBC, 0x5FFBBD, 0x5FFCC0, 0x5FFCC1, 0x5FFCC2, 0x5FFCC4,
+    0x5FFCC5, 0x5FFCC6, 0x5FFCC7, 0x5FFCC8, 0x5FFCC9, 0x5FFCCA, 0x5FFCCB, 0x5FFCCD,
+    0x5FFDD0, 0x5FFDD1, 0x5FFDD2, 0x5FFDD4, 0x5FFDD5, 0x5FFDD6, 0x5FFDD7, 0x5FFDD8,
+    0x5FFDD9, 0x5FFDDA, 0x5FFDDB, 0x5FFDDC, 0x6FF014, 0x6FF015, 0x6FF016, 0x6FF017,
+    0x6FF018, 0x6FF019, 0x6FF01A, 0x6FF01B, 0x6FF01C, 0x6FF01D, 0x6FF024, 0x6FF025,
+    0x6FF026, 0x6FF027, 0x6FF028, 0x6FF029, 0x6FF02A, 0x6FF02B, 0x6FF02C, 0x6FF02D,
+    0x6FF045, 0x6FF046, 0x6FF047, 0x6FF048, 0x6FF049, 0x6FF04A, 0x6FF04B, 0x6FF04C,
+    0x6FF04D, 0x6FF056, 0x6FF057, 0x6FF058, 0x6FF059, 0x6FF05A, 0x6FF05B, 0x6FF05C,
+    0x6FF05D, 0x6FF067, 0x6FF068, 0x6FF069, 0x6FF06A, 0x6FF06B, 0x6FF06C, 0x6FF06D,
+    0x6FF078, 0x6FF079, 0x6FF07A, 0x6FF07B, 0x6FF07C, 0x6FF07D, 0x6FF089, 0x6FF08A,
+    0x6FF08B, 0x6FF08C, 0x6FF08D, 0x6FF09A, 0x6FF09B, 0x6FF09C, 0x6FF09D, 0x6FF0AB,
+    0x6FF0AC, 0x6FF0AD, 0x6FF0BC, 0x6FF0BD, 0x6FF125, 0x6FF126, 0x6FF127, 0x6FF128,
+    0x6FF129, 0x6FF12A, 0x6FF12B, 0x6FF12C, 0x6FF12D, 0x6FF145, 0x6FF146, 0x6FF147,
+    0x6FF148, 0x6FF149, 0x6FF14A, 0x6FF14B, 0x6FF14C, 0x6FF14D, 0x6FF156, 0x6FF157,
+    0x6FF158, 0x6FF159, 0x6FF15A, 0x6FF15B, 0x6FF15C, 0x6FF15D, 0x6FF167, 0x6FF168,
+    0x6FF169, 0x6FF16A, 0x6FF16B, 0x6FF16C, 0x6FF16D, 0x6FF178, 0x6FF179, 0x6FF17A,
+    0x6FF17B, 0x6FF17C, 0x6FF17D, 0x6FF189, 0x6FF18A, 0x6FF18B, 0x6FF18C, 0x6FF18D,
+    0x6FF19A, 0x6FF19B, 0x6FF19C, 0x6FF19D, 0x6FF1AB, 0x6FF1AC, 0x6FF1AD, 0x6FF1BC,
+    0x6FF1BD, 0x6FF1CD, 0x6FF246, 0x6FF247, 0x6FF248, 0x6FF249, 0x6FF24A, 0x6FF24B,
+    0x6FF24C, 0x6FF24D, 0x6FF256, 0x6FF257, 0x6FF258, 0x6FF259, 0x6FF25A, 0x6FF25B,
+    0x6FF25C, 0x6FF25D, 0x6FF267, 0x6FF268, 0x6FF269, 0x6FF26A, 0x6FF26B, 0x6FF26C,
+    0x6FF26D, 0x6FF278, 0x6FF279, 0x6FF27A, 0x6FF27B, 0x6FF27C, 0x6FF27D, 0x6FF289,
+    0x6FF28A, 0x6FF28B, 0x6FF28C, 0x6FF28D, 0x6FF29A, 0x6FF29B, 0x6FF29C, 0x6FF29D,
+    0x6FF2AB, 0x6FF2AC, 0x6FF2AD, 0x6FF2BC, 0x6FF2BD, 0x6FF2CD, 0x6FF457, 0x6FF458,
+    0x6FF459, 0x6FF45A, 0x6FF45B, 0x6FF45C, 0x6FF45D, 0x6FF467, 0x6FF468, 0x6FF469,
+    0x6FF46A, 0x6FF46B, 0x6FF46C, 0x6FF46D, 0x6FF478, 0x6FF479, 0x6FF47A, 0x6FF47B,
+    0x6FF47C, 0x6FF47D, 0x6FF489, 0x6FF48A, 0x6FF48B, 0x6FF48C, 0x6FF48D, 0x6FF49A,
+    0x6FF49B, 0x6FF49C, 0x6FF49D, 0x6FF4AB, 0x6FF4AC, 0x6FF4AD, 0x6FF4BC, 0x6FF4BD,
+    0x6FF4CD, 0x6FF568, 0x6FF569, 0x6FF56A, 0x6FF56B, 0x6FF56C, 0x6FF56D, 0x6FF578,
+    0x6FF579, 0x6FF57A, 0x6FF57B, 0x6FF57C, 0x6FF57D, 0x6FF589, 0x6FF58A, 0x6FF58B,
+    0x6FF58C, 0x6FF58D, 0x6FF59A, 0x6FF59B, 0x6FF59C, 0x6FF59D, 0x6FF5AB, 0x6FF5AC,
+    0x6FF5AD, 0x6FF5BC, 0x6FF5BD, 0x6FF5CD, 0x6FF679, 0x6FF67A, 0x6FF67B, 0x6FF67C,
+    0x6FF67D, 0x6FF689, 0x6FF68A, 0x6FF68B, 0x6FF68C, 0x6FF68D, 0x6FF69A, 0x6FF69B,
+    0x6FF69C, 0x6FF69D, 0x6FF6AB, 0x6FF6AC, 0x6FF6AD, 0x6FF6BC, 0x6FF6BD, 0x6FF6CD,
+    0x6FF78A, 0x6FF78B, 0x6FF78C, 0x6FF78D, 0x6FF79A, 0x6FF79B, 0x6FF79C, 0x6FF79D,
+    0x6FF7AB, 0x6FF7AC, 0x6FF7AD, 0x6FF7BC, 0x6FF7BD, 0x6FF7CD, 0x6FF89B, 0x6FF89C,
+    0x6FF89D, 0x6FF8AB, 0x6FF8AC, 0x6FF8AD, 0x6FF8BC, 0x6FF8BD, 0x6FF8CD, 0x6FF9AC,
+    0x6FF9AD, 0x6FF9BC, 0x6FF9BD, 0x6FF9CD, 0x6FFABD, 0x6FFACD,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn test_validate_tables() -> Result<()> {
+        assert_eq!(OJP_TC_TABLE_1.len(), 22101);
+        assert_eq!(OJP_TC_TABLE_2.len(), 742);
+
+        assert_eq!(OJP_TC_TABLE_1[1], 13);
+        assert_eq!(OJP_TC_TABLE_2[1], 0x1FF000);
+        assert_eq!(OJP_TC_TABLE_2[741], 0x6FFACD);
+
+        for i in 1..22101 {
+            let ec = OJP_TC_TABLE_1[i];
+            assert!(0 != ec && ec < 742);
+        }
+        for i in 1..742 {
+            let d = OJP_TC_TABLE_2[i];
+            assert!(0 != d && d < 0x03FF_FFFF);
+            assert!(d > OJP_TC_TABLE_2[i-1]);
+        }
+        Ok(())
+    }
+}
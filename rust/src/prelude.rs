@@ -31,6 +31,7 @@ pub use crate::cards::card_parse::card_parse;
 pub use crate::cards::deck_type::DeckType;
 pub use crate::cards::deck::Deck;
 pub use crate::cards::hand::Hand;
+pub use crate::cards::card_set::CardSet;
 pub use crate::poker::hand_scale::Scale;
 pub use crate::poker::hand_value::{
     HandLevel, HandValue, HandDescription,
@@ -318,6 +318,7 @@ pub const fn oj_binomial(n: i32, k: i32) -> u64 {
 
 /// Lookup table for binomial coefficients up to 63c63, calculated
 /// additively with Pascal's triangle method at compile time.
+#[allow(clippy::large_const_arrays)]
 pub const COEFFICIENTS: [[u64; 64]; 64] = {
     let mut n = 0;
     let mut table = [[0; 64]; 64];
@@ -358,9 +359,9 @@ mod tests {
                 }
             }
         }
-        for i in 0..20 {
-            assert!(counts[i] > 49000);
-            assert!(counts[i] < 51000);
+        for c in counts {
+            assert!(c > 49000);
+            assert!(c < 51000);
         }
         Ok(())
     }
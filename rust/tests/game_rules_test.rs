@@ -0,0 +1,28 @@
+//@ tests/game_rules_test.rs
+
+use onejoker::prelude::*;
+#[cfg(feature = "serde")]
+use onejoker::poker::{GameRegistry, GameRules};
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_master_games_file() -> OjResult<()> {
+    let data = GameRegistry::read("../data/json/master_games.jsonc")?;
+    let builtin = GameRegistry::builtin();
+    assert_eq!(data.len(), builtin.len());
+
+    for g in data.iter() {
+        let b = GameRules::by_name(&g.name).unwrap();
+        assert_eq!(g, b);
+
+        for a in g.aliases.iter() {
+            assert_eq!(builtin.get(a), Some(b));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_no_json() -> OjResult<()> {
+    Ok(())
+}